@@ -2,10 +2,15 @@
 
 #include "transys.h"
 #include "giputils.h"
+#include <string>
 
 extern "C" {
 void *gipsat_new(const void *);
 
+void *gipsat_new_shared(const void *);
+
+int gipsat_is_shared(void *);
+
 void gipsat_drop(void *);
 
 size_t gipsat_level(void *);
@@ -18,17 +23,67 @@ int gipsat_inductive(void *, uint, uint *, uint, int);
 
 class RustVec gipsat_inductive_core(void *);
 
+class RustVec gipsat_minimal_inductive_core(void *, int, uint);
+
 class RustVec gipsat_get_predecessor(void *);
 
+class RustVec gipsat_get_predecessor_ternary(void *);
+
+class RustVec gipsat_complete_model(void *);
+
 int gipsat_propagate(void *);
 
 bool gipsat_has_bad(void *);
 
+int gipsat_add_property(void *, uint *, uint);
+
+int gipsat_has_bad_property(void *, uint);
+
+int gipsat_has_bad_any(void *);
+
+void gipsat_set_proved(void *, uint);
+
+void gipsat_set_falsified(void *, uint);
+
 void gipsat_set_domain(void *, int, uint *, uint);
 
 void gipsat_unset_domain(void *, int);
 
 void gipsat_statistic(void *);
+
+class RustVec gipsat_generalize(void *, uint, uint *, uint, uint, uint);
+
+double gipsat_frame_activity(void *, uint, uint);
+
+double gipsat_activity(void *, uint);
+
+int gipsat_bump_activity(void *, uint *, uint);
+
+void gipsat_decay_activity(void *);
+
+void gipsat_set_aig_rst(void *, int);
+
+int gipsat_record(void *, const char *);
+
+int gipsat_stop_record(void *);
+
+int gipsat_export_dimacs(void *, uint, uint *, uint, int, int, const char *);
+
+void gipsat_set_name(void *, uint, const char *);
+
+class RustVec gipsat_statistics_json(void *);
+
+size_t gipsat_frame_size(void *, uint);
+
+class RustVec gipsat_frame_lemma(void *, uint, uint);
+
+class RustVec gipsat_blocked_by(void *, uint, uint *, uint);
+
+int gipsat_lemma_origin(void *, uint *, uint, uint *, uint *);
+
+int gipsat_remove_lemma(void *, uint, uint *, uint);
+
+void gipsat_reset_to_level(void *, uint);
 }
 
 class GipSAT {
@@ -38,6 +93,16 @@ class GipSAT {
 		ptr = gipsat_new(transys.ptr);
 	}
 
+	GipSAT(Transys &transys, bool shared)
+	{
+		ptr = shared ? gipsat_new_shared(transys.ptr) : gipsat_new(transys.ptr);
+	}
+
+	bool is_shared()
+	{
+		return gipsat_is_shared(ptr);
+	}
+
 	~GipSAT()
 	{
 		gipsat_drop(ptr);
@@ -74,6 +139,17 @@ class GipSAT {
 		return res;
 	}
 
+	std::vector<uint> minimal_inductive_core(bool quickxplain, uint budget)
+	{
+		RustVec rv = gipsat_minimal_inductive_core(ptr, quickxplain, budget);
+		std::vector<uint> res;
+		uint *data = (uint *)rv.data();
+		for (int i = 0; i < rv.size(); ++i) {
+			res.push_back(*(data + i));
+		}
+		return res;
+	}
+
 	std::vector<uint> get_predecessor()
 	{
 		RustVec rv = gipsat_get_predecessor(ptr);
@@ -85,6 +161,29 @@ class GipSAT {
 		return res;
 	}
 
+	std::vector<uint> get_predecessor_ternary()
+	{
+		RustVec rv = gipsat_get_predecessor_ternary(ptr);
+		std::vector<uint> res;
+		uint *data = (uint *)rv.data();
+		for (int i = 0; i < rv.size(); ++i) {
+			res.push_back(*(data + i));
+		}
+		return res;
+	}
+
+	// empty if the model can not be completed
+	std::vector<uint> complete_model()
+	{
+		RustVec rv = gipsat_complete_model(ptr);
+		std::vector<uint> res;
+		uint *data = (uint *)rv.data();
+		for (int i = 0; i < rv.size(); ++i) {
+			res.push_back(*(data + i));
+		}
+		return res;
+	}
+
 	bool propagate()
 	{
 		return gipsat_propagate(ptr) == 1;
@@ -95,6 +194,31 @@ class GipSAT {
 		return gipsat_has_bad(ptr) == 1;
 	}
 
+	int add_property(std::vector<uint> &bad)
+	{
+		return gipsat_add_property(ptr, bad.data(), bad.size());
+	}
+
+	bool has_bad_property(uint property)
+	{
+		return gipsat_has_bad_property(ptr, property) == 1;
+	}
+
+	int has_bad_any()
+	{
+		return gipsat_has_bad_any(ptr);
+	}
+
+	void set_proved(uint property)
+	{
+		gipsat_set_proved(ptr, property);
+	}
+
+	void set_falsified(uint property)
+	{
+		gipsat_set_falsified(ptr, property);
+	}
+
 	void set_domain(uint frame, std::vector<uint> &d)
 	{
 		gipsat_set_domain(ptr, frame, d.data(), d.size());
@@ -110,6 +234,111 @@ class GipSAT {
 		gipsat_statistic(ptr);
 	}
 
+	std::string statistics_json()
+	{
+		RustVec rv = gipsat_statistics_json(ptr);
+		return std::string((char *)rv.data(), rv.size());
+	}
+
+	std::vector<uint> generalize(uint frame, std::vector<uint> &cube, uint ctg_depth, uint ctg_count)
+	{
+		RustVec rv = gipsat_generalize(ptr, frame, cube.data(), cube.size(), ctg_depth, ctg_count);
+		std::vector<uint> res;
+		uint *data = (uint *)rv.data();
+		for (int i = 0; i < rv.size(); ++i) {
+			res.push_back(*(data + i));
+		}
+		return res;
+	}
+
+	double frame_activity(uint frame, uint var)
+	{
+		return gipsat_frame_activity(ptr, frame, var);
+	}
+
+	double activity(uint var)
+	{
+		return gipsat_activity(ptr, var);
+	}
+
+	bool bump_activity(std::vector<uint> &cube)
+	{
+		return gipsat_bump_activity(ptr, cube.data(), cube.size()) == 0;
+	}
+
+	void decay_activity()
+	{
+		gipsat_decay_activity(ptr);
+	}
+
+	void set_aig_rst(bool rst)
+	{
+		gipsat_set_aig_rst(ptr, rst);
+	}
+
+	bool record(const char *path)
+	{
+		return gipsat_record(ptr, path) == 0;
+	}
+
+	bool stop_record()
+	{
+		return gipsat_stop_record(ptr) == 0;
+	}
+
+	void set_name(uint var, const char *name)
+	{
+		gipsat_set_name(ptr, var, name);
+	}
+
+	bool export_dimacs(uint frame, std::vector<uint> &cube, bool strengthen, bool learnt, const char *path)
+	{
+		return gipsat_export_dimacs(ptr, frame, cube.data(), cube.size(), strengthen, learnt, path) == 0;
+	}
+
+	size_t frame_size(uint frame)
+	{
+		return gipsat_frame_size(ptr, frame);
+	}
+
+	// empty if the frame or the index is out of range
+	std::vector<uint> frame_lemma(uint frame, uint idx)
+	{
+		RustVec rv = gipsat_frame_lemma(ptr, frame, idx);
+		std::vector<uint> res;
+		uint *data = (uint *)rv.data();
+		for (int i = 0; i < rv.size(); ++i) {
+			res.push_back(*(data + i));
+		}
+		return res;
+	}
+
+	std::vector<uint> blocked_by(uint frame, std::vector<uint> &cube)
+	{
+		RustVec rv = gipsat_blocked_by(ptr, frame, cube.data(), cube.size());
+		std::vector<uint> res;
+		uint *data = (uint *)rv.data();
+		for (int i = 0; i < rv.size(); ++i) {
+			res.push_back(*(data + i));
+		}
+		return res;
+	}
+
+	bool lemma_origin(std::vector<uint> &cube, uint &frame, uint &begin)
+	{
+		return gipsat_lemma_origin(ptr, cube.data(), cube.size(), &frame, &begin) == 1;
+	}
+
+	bool remove_lemma(uint frame, std::vector<uint> &cube)
+	{
+		return gipsat_remove_lemma(ptr, frame, cube.data(), cube.size()) == 1;
+	}
+
+	void reset_to_level(uint level)
+	{
+		gipsat_reset_to_level(ptr, level);
+	}
+
     private:
 	void *ptr;
 };
\ No newline at end of file
@@ -0,0 +1,261 @@
+use crate::{
+    cdb::ClauseKind,
+    ic3::{IC3Result, Step, IC3},
+    liveness::new_latch,
+    Frame, GeneralizeConfig, Sat, Solver, Unsat,
+};
+use logic_form::{Clause, Cube, Lit, Var, VarMap};
+use satif::{SatResult, SatifSat, SatifUnsat};
+use std::rc::Rc;
+use transys::Transys;
+
+impl Solver {
+    pub(crate) fn solve_without_domain(&mut self, assump: &[Lit]) -> SatResult<Sat, Unsat> {
+        self.backtrack(0, false);
+        self.clean_temporary();
+        for v in 0..self.num_var() {
+            self.domain.local.insert(Var::new(v));
+        }
+        self.vsids.enable_bucket = false;
+        self.vsids.heap.clear();
+        for d in self.domain.domains() {
+            if self.value.v(d.lit()).is_none() {
+                self.vsids.push(*d);
+            }
+        }
+        self.statistic.num_solve += 1;
+        self.search_with_restart(assump)
+    }
+}
+
+/// localization abstraction, latchs outside the abstraction are freed as inputs,
+/// spurious counterexamples are refined with a bounded concrete check. one IC3
+/// engine is kept over all refinements, each latch outside the initial abstraction
+/// is replaced by a shadow latch and follows it only when its activation input is
+/// set, refining the latch asserts the activation permanently
+pub struct Abstraction {
+    ts: Rc<Transys>,
+    abs: VarMap<bool>,
+    act: VarMap<Option<Lit>>,
+    ic3: IC3,
+}
+
+impl Abstraction {
+    pub fn new(ts: Rc<Transys>, config: GeneralizeConfig) -> Self {
+        let mut abs = VarMap::new();
+        abs.reserve(Var::new(ts.num_var));
+        let mut act = VarMap::new();
+        act.reserve(Var::new(ts.num_var));
+        let mut marked = VarMap::new();
+        marked.reserve(Var::new(ts.num_var));
+        let mut queue: Vec<Var> = ts
+            .bad
+            .iter()
+            .chain(ts.constraints.iter())
+            .map(|l| l.var())
+            .collect();
+        for v in queue.iter() {
+            marked[*v] = true;
+        }
+        while let Some(v) = queue.pop() {
+            for d in ts.dependence[v].iter() {
+                if !marked[*d] {
+                    marked[*d] = true;
+                    queue.push(*d);
+                }
+            }
+        }
+        let mut abs_ts = ts.as_ref().clone();
+        for l in ts.latchs.iter() {
+            abs[*l] = marked[*l];
+            if !abs[*l] {
+                act[*l] = Some(free_latch(&mut abs_ts, *l));
+            }
+        }
+        let ic3 = IC3::new(Rc::new(abs_ts), config);
+        Self { ts, abs, act, ic3 }
+    }
+
+    /// get the latchs in the abstraction
+    pub fn latchs(&self) -> Vec<Var> {
+        self.ts
+            .latchs
+            .iter()
+            .filter(|l| self.abs[**l])
+            .copied()
+            .collect()
+    }
+
+    /// check the counterexample length on the concrete system, returns the
+    /// concrete trace, or the freed latchs needed to refute it, none if no initial
+    /// state satisfies the constraints
+    fn bmc(&self, depth: usize) -> Option<Result<Vec<Step>, Vec<Var>>> {
+        let ts = &self.ts;
+        let frame = Frame::default();
+        let mut solver = Solver::new(None, ts, &frame);
+        let shift = |l: Lit, k: usize| {
+            Lit::new(
+                Var::new(Into::<usize>::into(l.var()) + k * ts.num_var),
+                l.polarity(),
+            )
+        };
+        while solver.num_var() < ts.num_var * (depth + 1) {
+            solver.new_var();
+        }
+        for k in 1..=depth {
+            for cls in ts.trans.iter() {
+                let cls: Clause = cls.iter().map(|l| shift(*l, k)).collect();
+                solver.add_clause_inner(&cls, ClauseKind::Trans);
+            }
+        }
+        // the initial states and the constraints are assumed, they may conflict
+        let mut assump = Cube::new();
+        for k in 0..=depth {
+            assump.extend(ts.constraints.iter().map(|c| shift(*c, k)));
+        }
+        let mut act = Vec::new();
+        for l in ts.latchs.iter() {
+            let guard = if self.abs[*l] {
+                None
+            } else {
+                let a = solver.new_var().lit();
+                act.push((*l, a));
+                Some(!a)
+            };
+            let mut clauses = Vec::new();
+            if let Some(init) = ts.init_map.get(l) {
+                match guard {
+                    Some(guard) => clauses.push(Clause::from([Lit::new(*l, *init), guard])),
+                    None => assump.push(Lit::new(*l, *init)),
+                }
+            }
+            for k in 0..depth {
+                let now = shift(l.lit(), k + 1);
+                let next = shift(ts.lit_next(l.lit()), k);
+                clauses.push(Clause::from([!now, next]));
+                clauses.push(Clause::from([now, !next]));
+            }
+            for mut cls in clauses {
+                cls.extend(guard);
+                solver.add_clause_inner(&cls, ClauseKind::Trans);
+            }
+        }
+        let init: Cube = ts
+            .init
+            .iter()
+            .filter(|l| self.abs[**l])
+            .copied()
+            .chain(ts.constraints.iter().copied())
+            .collect();
+        if let SatResult::Unsat(_) = solver.solve_without_domain(&init) {
+            return None;
+        }
+        assump.extend(act.iter().map(|(_, a)| *a));
+        assump.extend(ts.bad.iter().map(|l| shift(*l, depth)));
+        Some(match solver.solve_without_domain(&assump) {
+            SatResult::Sat(sat) => {
+                let value = |vars: &[Var], k: usize| -> Cube {
+                    vars.iter()
+                        .filter_map(|v| sat.lit_value(shift(v.lit(), k)).map(|b| Lit::new(*v, b)))
+                        .collect()
+                };
+                Ok((0..=depth)
+                    .map(|k| Step {
+                        latchs: value(&ts.latchs, k),
+                        inputs: value(&ts.inputs, k),
+                    })
+                    .collect())
+            }
+            SatResult::Unsat(unsat) => Err(act
+                .into_iter()
+                .filter(|(_, a)| unsat.has(*a))
+                .map(|(l, _)| l)
+                .collect()),
+        })
+    }
+
+    pub fn check(&mut self) -> IC3Result {
+        loop {
+            let trace = match self.ic3.check() {
+                IC3Result::Safe => return IC3Result::Safe,
+                IC3Result::Unsafe(trace) => trace,
+            };
+            match self.bmc(trace.len() - 1) {
+                None => return IC3Result::Safe,
+                Some(Ok(trace)) => return IC3Result::Unsafe(trace),
+                Some(Err(refine)) => {
+                    let refine = if refine.is_empty() {
+                        self.ts.latchs.clone()
+                    } else {
+                        refine
+                    };
+                    for l in refine {
+                        if let Some(act) = self.act[l].take() {
+                            self.ic3.add_constraint(Clause::from([act]));
+                        }
+                        self.abs[l] = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// free the latch in the transition system, the latch becomes a gate taking the
+/// value of a new shadow latch with its transition if the returned activation
+/// input is set and the value of a new free input otherwise
+fn free_latch(ts: &mut Transys, latch: Var) -> Lit {
+    let init = ts.init_map.remove(&latch);
+    let next = ts.lit_next(latch.lit());
+    ts.latchs.retain(|l| *l != latch);
+    ts.init.retain(|l| l.var() != latch);
+    let shadow = new_latch(ts, |_, _| next, init);
+    let act = ts.new_var();
+    let free = ts.new_var();
+    ts.dependence.reserve(free);
+    ts.inputs.extend([act, free]);
+    let (l, act, free) = (latch.lit(), act.lit(), free.lit());
+    ts.trans.extend([
+        Clause::from([!act, !shadow, l]),
+        Clause::from([!act, shadow, !l]),
+        Clause::from([act, !free, l]),
+        Clause::from([act, free, !l]),
+    ]);
+    ts.dependence[latch] = vec![act.var(), shadow.var(), free.var()];
+    act
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Abstraction;
+    use crate::{
+        testing::{assert_counterexample, load},
+        GeneralizeConfig, IC3Result,
+    };
+
+    /// latch x taking latch y, y stays zero, x is bad
+    const STUCK: &str = "aag 2 0 2 1 0\n2 4\n4 4\n2\n";
+
+    /// latch x taking latch y, y toggles, x is bad
+    const TOGGLE: &str = "aag 2 0 2 1 0\n2 4\n4 5\n2\n";
+
+    #[test]
+    fn refinement_removes_spurious_counterexample() {
+        let ts = load("abstraction_safe", STUCK);
+        let mut abs = Abstraction::new(ts.clone(), GeneralizeConfig::default());
+        assert_eq!(abs.latchs(), [ts.latchs[0]]);
+        assert!(matches!(abs.check(), IC3Result::Safe));
+        assert_eq!(abs.latchs(), ts.latchs);
+    }
+
+    #[test]
+    fn refined_counterexample_is_concrete() {
+        let ts = load("abstraction_unsafe", TOGGLE);
+        let mut abs = Abstraction::new(ts.clone(), GeneralizeConfig::default());
+        let IC3Result::Unsafe(trace) = abs.check() else {
+            panic!("no counterexample found");
+        };
+        assert_eq!(trace.len(), 3);
+        assert_counterexample(&ts, &trace);
+    }
+}
@@ -18,6 +18,8 @@ pub enum Mark {
 pub struct Analyze {
     mark: VarMap<Mark>,
     clear: Vec<Lit>,
+    /// whether the last learnt clause is derived from transition clauses only
+    pub trans_only: bool,
 }
 
 impl Analyze {
@@ -74,9 +76,16 @@ impl Solver {
         let mut stack: Vec<(Lit, usize)> = vec![(lit, 1)];
         'a: while let Some((p, b)) = stack.pop() {
             let c = self.cdb.get(self.reason[p]);
+            if !c.is_trans_only() {
+                self.analyze.trans_only = false;
+            }
             for i in b..c.len() {
                 let l = c[i];
-                if self.level[l] == 0 || matches!(self.analyze[l], Mark::Seen | Mark::Removable) {
+                if self.level[l] == 0 {
+                    self.analyze.trans_only = false;
+                    continue;
+                }
+                if matches!(self.analyze[l], Mark::Seen | Mark::Removable) {
                     continue;
                 }
                 if self.reason[l] == CREF_NONE || matches!(self.analyze[l], Mark::Failed) {
@@ -129,12 +138,19 @@ impl Solver {
         let mut path = 0;
         let mut trail_idx = self.trail.len() - 1;
         let mut resolve_lit = None;
+        self.analyze.trans_only = true;
         loop {
             self.cdb.bump(conflict);
             let cref = self.cdb.get(conflict);
+            if !cref.is_trans_only() {
+                self.analyze.trans_only = false;
+            }
             let begin = if resolve_lit.is_some() { 1 } else { 0 };
             for lit in begin..cref.len() {
                 let lit = cref[lit];
+                if self.level[lit] == 0 {
+                    self.analyze.trans_only = false;
+                }
                 if !self.analyze.seen(lit) && self.level[lit] > 0 {
                     self.vsids.bump(lit.var());
                     self.analyze[lit] = Mark::Seen;
@@ -0,0 +1,31 @@
+use aig::Aig;
+use clap::Parser;
+use std::{fs::File, io::BufReader, process::exit, rc::Rc};
+use transys::Transys;
+
+/// replay a call record of GipSAT and report the first divergence
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// aiger model the record was taken on
+    model: String,
+
+    /// record file written by GipSAT::record
+    record: String,
+}
+
+fn main() {
+    let args = Args::parse();
+    let aig = Aig::from_file(&args.model);
+    let header = BufReader::new(File::open(&args.record).unwrap());
+    let rst = gipsat::record_aig_rst(header).unwrap().unwrap_or(true);
+    let ts = Rc::new(Transys::from_aig(&aig, rst));
+    let record = BufReader::new(File::open(&args.record).unwrap());
+    match gipsat::replay(ts, record).unwrap() {
+        Ok(num_call) => println!("replayed {num_call} calls without divergence"),
+        Err(divergence) => {
+            println!("{divergence}");
+            exit(1);
+        }
+    }
+}
@@ -15,7 +15,8 @@ struct Header {
     learnt: bool,
     reloced: bool,
     marked: bool,
-    #[bits(28)]
+    trans_only: bool,
+    #[bits(27)]
     len: usize,
 }
 
@@ -50,6 +51,17 @@ impl Clause {
         unsafe { (*self.data).header.learnt() }
     }
 
+    /// whether the clause is derived from transition clauses only
+    #[inline]
+    pub fn is_trans_only(&self) -> bool {
+        unsafe { (*self.data).header.trans_only() }
+    }
+
+    #[inline]
+    pub fn set_trans_only(&mut self, trans_only: bool) {
+        unsafe { (*self.data).header.set_trans_only(trans_only) }
+    }
+
     #[inline]
     pub fn is_marked(&self) -> bool {
         unsafe { (*self.data).header.marked() }
@@ -285,6 +297,51 @@ impl ClauseDB {
         self.act_inc *= 1.0 / Self::DECAY
     }
 
+    /// get the transition clauses, together with the learnt and temporary clauses
+    /// if asked
+    pub fn clauses(&self, learnt: bool, temporary: bool) -> Vec<CRef> {
+        let mut clauses = Vec::new();
+        for i in 0..self.trans.len() {
+            clauses.push(self.trans[i]);
+        }
+        if learnt {
+            for i in 0..self.learnt.len() {
+                clauses.push(self.learnt[i]);
+            }
+        }
+        if temporary {
+            for i in 0..self.temporary.len() {
+                clauses.push(self.temporary[i]);
+            }
+        }
+        clauses
+    }
+
+    /// mark the current transition clauses as derived from transition clauses only
+    pub fn mark_trans_only(&mut self) {
+        for i in 0..self.trans.len() {
+            self.get(self.trans[i]).set_trans_only(true);
+        }
+    }
+
+    /// ratio of learnt clauses to transition clauses
+    #[inline]
+    pub fn learnt_ratio(&self) -> f64 {
+        self.learnt.len() as f64 / self.trans.len().max(1) as f64
+    }
+
+    /// ratio of wasted memory in the allocator
+    #[inline]
+    pub fn wasted_ratio(&mut self) -> f64 {
+        self.allocator.wasted as f64 / self.allocator.len().max(1) as f64
+    }
+
+    /// bytes allocated for clauses
+    #[inline]
+    pub fn memory(&self) -> usize {
+        self.allocator.data.len() * size_of::<Data>()
+    }
+
     #[inline]
     #[allow(unused)]
     pub fn num_leanrt(&self) -> u32 {
@@ -340,6 +397,37 @@ impl Solver {
         self.value.v(cls[0]).is_true() && self.reason[cls[0]] == cref
     }
 
+    /// remove the clause of a lemma which no longer holds together with the learnt
+    /// clauses not derived from the transition relation only, returns false if an
+    /// assignment of level zero may depend on them, the solver has to be rebuilt
+    pub fn retract_lemma(&mut self, cref: CRef) -> bool {
+        self.backtrack(0, false);
+        self.clean_temporary();
+        if self.lemma_unit || self.root_conflict || cref == CREF_NONE || self.locked(cref) {
+            return false;
+        }
+        let derived = |s: &Self, l: &CRef| !s.cdb.get(*l).is_trans_only();
+        if self
+            .cdb
+            .learnt
+            .iter()
+            .any(|l| derived(self, l) && self.locked(*l))
+        {
+            return false;
+        }
+        self.remove_clause(cref);
+        let learnt = take(&mut self.cdb.learnt);
+        for l in learnt.iter() {
+            if derived(self, l) {
+                self.remove_clause(*l);
+                self.statistic.num_deleted += 1;
+            } else {
+                self.cdb.learnt.push(*l);
+            }
+        }
+        true
+    }
+
     pub fn clean_leanrt(&mut self) {
         if (self.highest_level() == 0 && self.cdb.learnt.len() * 15 >= self.cdb.trans.len())
             || self.cdb.learnt.len() * 3 >= self.cdb.trans.len()
@@ -358,6 +446,7 @@ impl Solver {
                 let cls = self.cdb.get(l);
                 if i > learnt.len() / 3 && !self.locked(l) && cls.len() > 2 {
                     self.remove_clause(l);
+                    self.statistic.num_deleted += 1;
                 } else {
                     self.cdb.learnt.push(l);
                 }
@@ -379,6 +468,7 @@ impl Solver {
             while j < cls.len() {
                 if self.value.v(cls[j]).is_false() {
                     cls.swap_remove(j);
+                    cls.set_trans_only(false);
                     continue;
                 }
                 j += 1;
@@ -399,6 +489,8 @@ impl Solver {
 
     pub fn garbage_collect(&mut self) {
         if self.cdb.allocator.wasted * 3 > self.cdb.allocator.len() {
+            self.statistic.num_gc += 1;
+            self.observer.garbage_collect(self.id);
             let mut to =
                 Allocator::with_capacity(self.cdb.allocator.len() - self.cdb.allocator.wasted);
 
@@ -0,0 +1,50 @@
+use crate::{cdb::ClauseKind, Frame, Sat, Solver, Unsat};
+use logic_form::{Cube, Lit};
+use satif::SatResult;
+
+impl Solver {
+    fn checker(&self) -> Solver {
+        let mut checker = Solver::new(None, &self.ts, &Frame::default());
+        while checker.num_var() < self.num_var() {
+            checker.new_var();
+        }
+        for cls in self.clauses(false, true) {
+            checker.add_clause_inner(&cls, ClauseKind::Trans);
+        }
+        checker
+    }
+
+    /// validate the answer of a domain restricted query with an unrestricted solve,
+    /// a model must extend to all clauses and a core must stay unsatisfiable
+    pub(crate) fn check_domain(&mut self, res: &SatResult<Sat, Unsat>, assump: &[Lit]) {
+        match res {
+            SatResult::Sat(_) => {
+                let mut model = Cube::new();
+                for i in 0..self.trail.len() {
+                    model.push(self.trail[i]);
+                }
+                let mut checker = self.checker();
+                if let SatResult::Unsat(_) = checker.solve_without_domain(&model) {
+                    panic!(
+                        "domain check failed: model of solver {:?} can not be extended, assumption {:?}",
+                        self.id, assump
+                    );
+                }
+            }
+            SatResult::Unsat(_) => {
+                let core: Cube = assump
+                    .iter()
+                    .filter(|l| self.unsat_core.has(**l))
+                    .copied()
+                    .collect();
+                let mut checker = self.checker();
+                if let SatResult::Sat(_) = checker.solve_without_domain(&core) {
+                    panic!(
+                        "domain check failed: core {:?} of solver {:?} is satisfiable, assumption {:?}",
+                        core, self.id, assump
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,9 @@
-use crate::GipSAT;
+use crate::{CoreMinimize, GeneralizeConfig, GipSAT, LiftMode};
 use core::ffi::c_size_t;
 use giputils::crffi::RustVec;
-use logic_form::{Cube, Lit};
+use logic_form::{Cube, Lit, Var};
 use std::{
-    ffi::{c_int, c_uint},
+    ffi::{c_char, c_double, c_int, c_uint, CStr},
     mem::forget,
     os::raw::c_void,
     rc::Rc,
@@ -21,6 +21,22 @@ pub extern "C" fn gipsat_new(ts: *const c_void) -> *mut c_void {
     ptr
 }
 
+#[no_mangle]
+pub extern "C" fn gipsat_new_shared(ts: *const c_void) -> *mut c_void {
+    assert!(!ts.is_null());
+    let ts = unsafe { &*(ts as *const Transys) };
+    let gipsat = Box::new(GipSAT::new_shared(Rc::new(ts.clone())));
+    let ptr = gipsat.as_ref() as *const GipSAT as *mut c_void;
+    forget(gipsat);
+    ptr
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_is_shared(gipsat: *mut c_void) -> c_int {
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    gipsat.is_shared() as c_int
+}
+
 #[no_mangle]
 pub extern "C" fn gipsat_drop(gipsat: *mut c_void) {
     let gipsat: Box<GipSAT> = unsafe { Box::from_raw(gipsat as *mut _) };
@@ -76,6 +92,22 @@ pub extern "C" fn gipsat_inductive_core(gipsat: *mut c_void) -> RustVec {
     RustVec::new(core)
 }
 
+#[no_mangle]
+pub extern "C" fn gipsat_minimal_inductive_core(
+    gipsat: *mut c_void,
+    quickxplain: c_int,
+    budget: c_uint,
+) -> RustVec {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    let method = if quickxplain == 1 {
+        CoreMinimize::QuickXplain
+    } else {
+        CoreMinimize::Deletion
+    };
+    let core: Vec<Lit> = gipsat.minimal_inductive_core(method, budget as _).into();
+    RustVec::new(core)
+}
+
 #[no_mangle]
 pub extern "C" fn gipsat_get_predecessor(gipsat: *mut c_void) -> RustVec {
     let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
@@ -83,6 +115,20 @@ pub extern "C" fn gipsat_get_predecessor(gipsat: *mut c_void) -> RustVec {
     RustVec::new(core)
 }
 
+#[no_mangle]
+pub extern "C" fn gipsat_get_predecessor_ternary(gipsat: *mut c_void) -> RustVec {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    let core: Vec<Lit> = gipsat.get_predecessor_with(LiftMode::Ternary).into();
+    RustVec::new(core)
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_complete_model(gipsat: *mut c_void) -> RustVec {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    let model: Vec<Lit> = gipsat.complete_model().unwrap_or_default().into();
+    RustVec::new(model)
+}
+
 #[no_mangle]
 pub extern "C" fn gipsat_propagate(gipsat: *mut c_void) -> c_int {
     let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
@@ -95,6 +141,47 @@ pub extern "C" fn gipsat_has_bad(gipsat: *mut c_void) -> c_int {
     gipsat.has_bad() as _
 }
 
+#[no_mangle]
+pub extern "C" fn gipsat_add_property(
+    gipsat: *mut c_void,
+    bad_ptr: *const c_uint,
+    bad_len: c_uint,
+) -> c_int {
+    let bad = unsafe { from_raw_parts(bad_ptr as *const Lit, bad_len as _) };
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    match gipsat.add_property(Cube::from(bad)) {
+        Some(property) => property as _,
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_has_bad_property(gipsat: *mut c_void, property: c_uint) -> c_int {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    gipsat.has_bad_property(property as _) as _
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_has_bad_any(gipsat: *mut c_void) -> c_int {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    match gipsat.has_bad_any() {
+        Some(property) => property as _,
+        None => -1,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_set_proved(gipsat: *mut c_void, property: c_uint) {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    gipsat.set_proved(property as _)
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_set_falsified(gipsat: *mut c_void, property: c_uint) {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    gipsat.set_falsified(property as _)
+}
+
 #[no_mangle]
 pub extern "C" fn gipsat_set_domain(
     gipsat: *mut c_void,
@@ -118,3 +205,214 @@ pub extern "C" fn gipsat_statistic(gipsat: *mut c_void) {
     let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
     gipsat.statistic()
 }
+
+#[no_mangle]
+pub extern "C" fn gipsat_generalize(
+    gipsat: *mut c_void,
+    frame: c_uint,
+    cube_ptr: *const c_uint,
+    cube_len: c_uint,
+    ctg_depth: c_uint,
+    ctg_count: c_uint,
+) -> RustVec {
+    let cube = unsafe { from_raw_parts(cube_ptr as *const Lit, cube_len as _) };
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    let config = GeneralizeConfig {
+        ctg_depth: ctg_depth as _,
+        ctg_count: ctg_count as _,
+        ..Default::default()
+    };
+    let res: Vec<Lit> = gipsat
+        .generalize(frame as _, Cube::from(cube), &config)
+        .into();
+    RustVec::new(res)
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_frame_activity(
+    gipsat: *mut c_void,
+    frame: c_uint,
+    var: c_uint,
+) -> c_double {
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    let frame = frame as usize;
+    if frame >= gipsat.frame.len()
+        || var as usize >= gipsat.solvers[gipsat.solver_of(frame)].num_var()
+    {
+        return -1.0;
+    }
+    gipsat.frame_activity(frame, Var::new(var as _))
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_activity(gipsat: *mut c_void, var: c_uint) -> c_double {
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    let var = Var::new(var as _);
+    if var > gipsat.ts.max_latch {
+        return -1.0;
+    }
+    gipsat.activity(var)
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_bump_activity(
+    gipsat: *mut c_void,
+    cube_ptr: *const c_uint,
+    cube_len: c_uint,
+) -> c_int {
+    let cube = unsafe { from_raw_parts(cube_ptr as *const Lit, cube_len as _) };
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    if cube.iter().any(|l| l.var() > gipsat.ts.max_latch) {
+        return -1;
+    }
+    gipsat.bump_activity(cube);
+    0
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_decay_activity(gipsat: *mut c_void) {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    gipsat.decay_activity()
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_set_aig_rst(gipsat: *mut c_void, rst: c_int) {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    gipsat.set_aig_rst(rst != 0)
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_record(gipsat: *mut c_void, path: *const c_char) -> c_int {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+        return -1;
+    };
+    match gipsat.record(path) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_stop_record(gipsat: *mut c_void) -> c_int {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    match gipsat.stop_record() {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_export_dimacs(
+    gipsat: *mut c_void,
+    frame: c_uint,
+    cube_ptr: *const c_uint,
+    cube_len: c_uint,
+    strengthen: c_int,
+    learnt: c_int,
+    path: *const c_char,
+) -> c_int {
+    let cube = unsafe { from_raw_parts(cube_ptr as *const Lit, cube_len as _) };
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    let Ok(path) = unsafe { CStr::from_ptr(path) }.to_str() else {
+        return -1;
+    };
+    match gipsat.export_dimacs(frame as _, cube, strengthen == 1, learnt == 1, path) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_set_name(gipsat: *mut c_void, var: c_uint, name: *const c_char) {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    let name = unsafe { CStr::from_ptr(name) };
+    gipsat.set_name(Var::new(var as _), name.to_string_lossy().into_owned())
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_statistics_json(gipsat: *mut c_void) -> RustVec {
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    RustVec::new(gipsat.statistics().to_json().into_bytes())
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_frame_size(gipsat: *mut c_void, frame: c_uint) -> c_size_t {
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    if frame as usize >= gipsat.frame.len() {
+        return 0;
+    }
+    gipsat.frame_size(frame as _) as _
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_frame_lemma(gipsat: *mut c_void, frame: c_uint, idx: c_uint) -> RustVec {
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    let lemma: Vec<Lit> = if frame as usize >= gipsat.frame.len() {
+        Vec::new()
+    } else {
+        match gipsat.frame_lemmas(frame as _).nth(idx as _) {
+            Some(lemma) => lemma.clone().into(),
+            None => Vec::new(),
+        }
+    };
+    RustVec::new(lemma)
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_blocked_by(
+    gipsat: *mut c_void,
+    frame: c_uint,
+    cube_ptr: *const c_uint,
+    cube_len: c_uint,
+) -> RustVec {
+    let cube = unsafe { from_raw_parts(cube_ptr as *const Lit, cube_len as _) };
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    let lemma: Vec<Lit> = match gipsat.blocked_by(frame as _, &Cube::from(cube)) {
+        Some(lemma) => lemma.clone().into(),
+        None => Vec::new(),
+    };
+    RustVec::new(lemma)
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_lemma_origin(
+    gipsat: *mut c_void,
+    cube_ptr: *const c_uint,
+    cube_len: c_uint,
+    frame: *mut c_uint,
+    begin: *mut c_uint,
+) -> c_int {
+    let cube = unsafe { from_raw_parts(cube_ptr as *const Lit, cube_len as _) };
+    let gipsat = unsafe { &*(gipsat as *const GipSAT) };
+    match gipsat.lemma_origin(&Cube::from(cube)) {
+        Some(origin) => {
+            unsafe {
+                *frame = origin.frame as _;
+                *begin = origin.begin as _;
+            }
+            1
+        }
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_remove_lemma(
+    gipsat: *mut c_void,
+    frame: c_uint,
+    cube_ptr: *const c_uint,
+    cube_len: c_uint,
+) -> c_int {
+    let cube = unsafe { from_raw_parts(cube_ptr as *const Lit, cube_len as _) };
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    gipsat.remove_lemma(frame as _, &Cube::from(cube)) as _
+}
+
+#[no_mangle]
+pub extern "C" fn gipsat_reset_to_level(gipsat: *mut c_void, level: c_uint) {
+    let gipsat = unsafe { &mut *(gipsat as *mut GipSAT) };
+    if (level as usize) < gipsat.frame.len() {
+        gipsat.reset_to_level(level as _)
+    }
+}
@@ -0,0 +1,52 @@
+use logic_form::{Lit, Var, VarMap};
+use transys::Transys;
+
+/// mark the variables in the sequential cone of influence of the roots
+fn coi_mark<'a>(ts: &Transys, roots: impl Iterator<Item = &'a Lit>) -> VarMap<bool> {
+    let max_var = Var::new(ts.num_var);
+    let mut is_latch: VarMap<bool> = VarMap::new();
+    is_latch.reserve(max_var);
+    for l in ts.latchs.iter() {
+        is_latch[*l] = true;
+    }
+    let mut marked: VarMap<bool> = VarMap::new();
+    marked.reserve(max_var);
+    let mut queue = Vec::new();
+    for l in roots {
+        if !marked[*l] {
+            marked[*l] = true;
+            queue.push(l.var());
+        }
+    }
+    while let Some(v) = queue.pop() {
+        let next = is_latch[v].then(|| ts.lit_next(v.lit()).var());
+        for d in ts.dependence[v].iter().chain(next.iter()) {
+            if !marked[*d] {
+                marked[*d] = true;
+                queue.push(*d);
+            }
+        }
+    }
+    marked
+}
+
+/// reduce the transition system to the sequential cone of influence of bad and
+/// constraints, variables are not renamed so cubes and models need no mapping
+pub fn coi_reduce(ts: &Transys) -> Transys {
+    let marked = coi_mark(ts, ts.bad.iter().chain(ts.constraints.iter()));
+    let mut ts = ts.clone();
+    ts.trans.retain(|cls| cls.iter().all(|l| marked[*l]));
+    ts.latchs.retain(|l| marked[*l]);
+    ts.inputs.retain(|l| marked[*l]);
+    ts.init.retain(|l| marked[*l]);
+    ts.init_map.retain(|l, _| marked[*l]);
+    ts
+}
+
+/// whether the sequential cone of influence of the roots lies in the reduced
+/// transition system
+pub fn coi_covers(ts: &Transys, reduced: &Transys, roots: &[Lit]) -> bool {
+    let marked = coi_mark(ts, reduced.bad.iter().chain(reduced.constraints.iter()));
+    let cone = coi_mark(ts, roots.iter());
+    (0..ts.num_var).all(|v| !cone[Var::new(v)] || marked[Var::new(v)])
+}
@@ -0,0 +1,116 @@
+use crate::{record::lit_to_dimacs, GipSAT, Solver, CREF_NONE};
+use logic_form::{Clause, Lit, Var};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+impl Solver {
+    /// clauses the solver currently reasons with, the lemmas of the frames and the
+    /// units of level zero included
+    pub(crate) fn clauses(&self, learnt: bool, temporary: bool) -> Vec<Clause> {
+        let mut clauses: Vec<Clause> = self
+            .cdb
+            .clauses(learnt, temporary)
+            .into_iter()
+            .map(|cref| Clause::from(self.cdb.get(cref).slice()))
+            .collect();
+        if let Some(id) = self.id {
+            for i in id..self.frame.len() {
+                for l in self.frame[i].iter() {
+                    let cref = l.get_cref(id);
+                    if cref != CREF_NONE {
+                        clauses.push(Clause::from(self.cdb.get(cref).slice()));
+                    }
+                }
+            }
+        }
+        let units = self
+            .pos_in_trail
+            .first()
+            .copied()
+            .unwrap_or(self.trail.len());
+        for i in 0..units {
+            clauses.push(Clause::from([self.trail[i]]));
+        }
+        clauses
+    }
+}
+
+impl GipSAT {
+    /// name a variable, the names of inputs and latchs are written in the comments
+    /// of exported dimacs files
+    pub fn set_name(&mut self, var: Var, name: String) {
+        self.names.insert(var, name);
+    }
+
+    fn name(&self, var: Var) -> String {
+        match self.names.get(&var) {
+            Some(name) => format!(" {name}"),
+            None => String::new(),
+        }
+    }
+
+    /// dump the query inductive(frame, cube, strengthen) as dimacs cnf, the
+    /// assumptions are written as unit clauses and the roles and names of the
+    /// variables as comments. the frame must be in 1..=level
+    pub fn export_dimacs(
+        &self,
+        frame: usize,
+        cube: &[Lit],
+        strengthen: bool,
+        learnt: bool,
+        path: impl AsRef<Path>,
+    ) -> io::Result<()> {
+        if frame == 0 || frame >= self.frame.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("no inductive query of frame {frame}"),
+            ));
+        }
+        let solver = &self.solvers[self.solver_of(frame - 1)];
+        let mut assumption = self.frame_act(frame - 1);
+        assumption.extend_from_slice(&self.ts.cube_next(cube));
+        let mut clauses = solver.clauses(learnt, false);
+        if strengthen {
+            clauses.push(Clause::from_iter(cube.iter().map(|l| !*l)));
+        }
+        for l in assumption.iter() {
+            clauses.push(Clause::from([*l]));
+        }
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "c inductive query of frame {frame}")?;
+        for input in self.ts.inputs.iter() {
+            writeln!(
+                file,
+                "c input {}{}",
+                lit_to_dimacs(input.lit()),
+                self.name(*input)
+            )?;
+        }
+        for latch in self.ts.latchs.iter() {
+            let next = self.ts.lit_next(latch.lit());
+            writeln!(
+                file,
+                "c latch {} next {}{}",
+                lit_to_dimacs(latch.lit()),
+                lit_to_dimacs(next),
+                self.name(*latch)
+            )?;
+        }
+        write!(file, "c assumption")?;
+        for l in assumption.iter() {
+            write!(file, " {}", lit_to_dimacs(*l))?;
+        }
+        writeln!(file)?;
+        writeln!(file, "p cnf {} {}", solver.num_var(), clauses.len())?;
+        for cls in clauses.iter() {
+            for l in cls.iter() {
+                write!(file, "{} ", lit_to_dimacs(*l))?;
+            }
+            writeln!(file, "0")?;
+        }
+        file.flush()
+    }
+}
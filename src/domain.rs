@@ -1,12 +1,72 @@
 use crate::search::Value;
-use logic_form::{Var, VarSet};
+use logic_form::{Var, VarMap, VarSet};
 use std::{collections::HashSet, rc::Rc, slice};
 use transys::Transys;
 
+/// cache of the combinational cone of each root variable, the cone of the current
+/// roots is kept as reference counts so that a new round only visits the cones of
+/// the roots that changed
+#[derive(Default)]
+struct ConeCache {
+    cone: VarMap<Option<Rc<[Var]>>>,
+    count: VarMap<u32>,
+    roots: Vec<Var>,
+    is_root: VarMap<bool>,
+    mark: VarMap<bool>,
+    size: usize,
+}
+
+impl ConeCache {
+    fn reserve(&mut self, var: Var) {
+        self.cone.reserve(var);
+        self.count.reserve(var);
+        self.is_root.reserve(var);
+        self.mark.reserve(var);
+    }
+
+    fn cone(&mut self, root: Var, ts: &Rc<Transys>) -> Rc<[Var]> {
+        if let Some(cone) = &self.cone[root] {
+            return cone.clone();
+        }
+        let mut marked = HashSet::new();
+        marked.insert(root);
+        let mut cone = vec![root];
+        let mut now = 0;
+        while now < cone.len() {
+            let v = cone[now];
+            now += 1;
+            if Into::<usize>::into(v) >= ts.num_var {
+                continue;
+            }
+            for d in ts.dependence[v].iter() {
+                if marked.insert(*d) {
+                    cone.push(*d);
+                }
+            }
+        }
+        cone.sort();
+        self.size += cone.len();
+        let cone: Rc<[Var]> = Rc::from(cone);
+        self.cone[root] = Some(cone.clone());
+        cone
+    }
+
+    fn clear(&mut self) {
+        for r in self.roots.iter() {
+            self.is_root[*r] = false;
+            for v in self.cone[*r].as_ref().unwrap().iter() {
+                self.count[*v] -= 1;
+            }
+        }
+        self.roots.clear();
+    }
+}
+
 pub struct Domain {
     pub lemma: VarSet,
     pub local: VarSet,
     pub constrain: u32,
+    cache: ConeCache,
 }
 
 impl Domain {
@@ -15,12 +75,14 @@ impl Domain {
             lemma: Default::default(),
             local: Default::default(),
             constrain: 0,
+            cache: Default::default(),
         }
     }
 
     pub fn reserve(&mut self, var: Var) {
         self.lemma.reserve(var);
         self.local.reserve(var);
+        self.cache.reserve(var);
     }
 
     pub fn calculate_constrain(&mut self, ts: &Rc<Transys>, value: &Value) {
@@ -61,6 +123,10 @@ impl Domain {
         while now < self.local.len() {
             let v = self.local[now];
             now += 1;
+            // variables out of the transition system, such as activation literals
+            if Into::<usize>::into(v) >= ts.num_var {
+                continue;
+            }
             for d in ts.dependence[v].iter() {
                 if value.v(d.lit()).is_none() {
                     self.local.insert(*d);
@@ -69,24 +135,89 @@ impl Domain {
         }
     }
 
-    pub fn enable_local(
+    pub fn add_constrain(
         &mut self,
-        domain: impl Iterator<Item = Var>,
+        root: impl Iterator<Item = Var>,
         ts: &Rc<Transys>,
         value: &Value,
     ) {
+        self.cache.clear();
         while self.local.len() > self.constrain {
             let v = self.local.set.pop().unwrap();
             self.local.has[v] = false;
         }
-        self.get_coi(domain, ts, value);
-        for l in self.lemma.iter() {
-            if value.v(l.lit()).is_none() {
-                self.local.insert(*l);
+        self.get_coi(root, ts, value);
+        self.constrain = self.local.len();
+    }
+
+    pub fn enable_local(
+        &mut self,
+        domain: impl Iterator<Item = Var>,
+        ts: &Rc<Transys>,
+        value: &Value,
+    ) {
+        let cache = &mut self.cache;
+        let mut roots = Vec::new();
+        for r in domain {
+            if !cache.mark[r] {
+                cache.mark[r] = true;
+                roots.push(r);
+            }
+        }
+        for r in cache.roots.iter() {
+            if !cache.mark[*r] {
+                cache.is_root[*r] = false;
+                for v in cache.cone[*r].as_ref().unwrap().iter() {
+                    cache.count[*v] -= 1;
+                }
+            }
+        }
+        let mut added = Vec::new();
+        for r in roots.iter() {
+            cache.mark[*r] = false;
+            if !cache.is_root[*r] {
+                cache.is_root[*r] = true;
+                for v in cache.cone(*r, ts).iter() {
+                    cache.count[*v] += 1;
+                    if cache.count[*v] == 1 {
+                        added.push(*v);
+                    }
+                }
+            }
+        }
+        cache.roots = roots;
+        let mut j = self.constrain as usize;
+        for i in self.constrain as usize..self.local.set.len() {
+            let v = self.local.set[i];
+            if (cache.count[v] > 0 || self.lemma.has(v)) && value.v(v.lit()).is_none() {
+                self.local.set[j] = v;
+                j += 1;
+            } else {
+                self.local.has[v] = false;
+            }
+        }
+        self.local.set.truncate(j);
+        for v in added.into_iter().chain(self.lemma.iter().copied()) {
+            if value.v(v.lit()).is_none() {
+                self.local.insert(v);
             }
         }
     }
 
+    /// precompute the cones of the roots, returns the number of cached variables
+    pub fn precompute_cone(&mut self, roots: impl Iterator<Item = Var>, ts: &Rc<Transys>) -> usize {
+        for r in roots {
+            self.cache.cone(r, ts);
+        }
+        self.cache.size
+    }
+
+    /// number of variables stored in the cached cones
+    #[inline]
+    pub fn cone_cache_size(&self) -> usize {
+        self.cache.size
+    }
+
     #[inline]
     pub fn has(&self, var: Var) -> bool {
         self.local.has(var)
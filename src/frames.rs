@@ -0,0 +1,48 @@
+use crate::{index::INF_FRAME, GipSAT};
+use logic_form::Cube;
+
+/// where a lemma is stored, the lemma is in frame and its clause is in the
+/// solvers from begin to frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LemmaOrigin {
+    pub frame: usize,
+    pub begin: usize,
+}
+
+impl GipSAT {
+    /// get the number of lemmas in the delta of the frame
+    #[inline]
+    pub fn frame_size(&self, frame: usize) -> usize {
+        self.frame[frame].len()
+    }
+
+    /// iterate the lemmas in the delta of the frame, the lemmas of a frame are
+    /// those of its delta and of all higher frames
+    pub fn frame_lemmas(&self, frame: usize) -> impl Iterator<Item = &Cube> {
+        self.frame[frame].iter().map(|l| l.cube())
+    }
+
+    /// find a lemma of the frame blocking the cube syntactically, the lemmas of
+    /// higher frames and the infinite frame are included
+    pub fn blocked_by(&self, frame: usize, cube: &Cube) -> Option<&Cube> {
+        let cube = logic_form::Lemma::new(cube.clone());
+        self.index
+            .find_subsumer(&cube, |f| f >= frame)
+            .map(|id| self.index.lemma(id).cube())
+    }
+
+    /// get where the lemma is stored, none if it is not in any frame
+    pub fn lemma_origin(&self, lemma: &Cube) -> Option<LemmaOrigin> {
+        let lemma = logic_form::Lemma::new(lemma.clone());
+        let (id, frame) = self
+            .index
+            .subsumed(&lemma, |f| f != INF_FRAME)
+            .into_iter()
+            .find(|(id, _)| *self.index.lemma(*id) == lemma)?;
+        let l = self.frame[frame].iter().find(|l| l.id == id).unwrap();
+        Some(LemmaOrigin {
+            frame,
+            begin: l.begin,
+        })
+    }
+}
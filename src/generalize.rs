@@ -0,0 +1,122 @@
+use crate::GipSAT;
+use logic_form::{Cube, LitSet};
+
+#[derive(Clone, Copy, Debug)]
+pub struct GeneralizeConfig {
+    /// max recursion depth of counterexample to generalization
+    pub ctg_depth: usize,
+    /// max number of ctgs handled when dropping one literal
+    pub ctg_count: usize,
+    /// drop literals with lower caller side activity first
+    pub activity_order: bool,
+}
+
+impl Default for GeneralizeConfig {
+    fn default() -> Self {
+        Self {
+            ctg_depth: 1,
+            ctg_count: 3,
+            activity_order: true,
+        }
+    }
+}
+
+impl GipSAT {
+    fn ctg_down(
+        &mut self,
+        frame: usize,
+        cube: &Cube,
+        depth: usize,
+        config: &GeneralizeConfig,
+    ) -> Option<Cube> {
+        let mut cube = cube.clone();
+        let mut ctg = 0;
+        loop {
+            if self.ts.cube_subsume_init(&cube) {
+                return None;
+            }
+            if self.inductive(frame, &cube, true) {
+                return Some(self.inductive_core());
+            }
+            if depth >= config.ctg_depth || ctg >= config.ctg_count {
+                return None;
+            }
+            let model = self.get_predecessor();
+            if frame > 1
+                && !self.ts.cube_subsume_init(&model)
+                && self.inductive(frame - 1, &model, true)
+            {
+                ctg += 1;
+                let mut core = self.inductive_core();
+                let mut push = frame - 1;
+                while push < self.level() && self.inductive(push + 1, &core, true) {
+                    core = self.inductive_core();
+                    push += 1;
+                }
+                let core = self.mic(push, core, depth + 1, config);
+                self.add_lemma(push, core);
+            } else {
+                ctg = 0;
+                let mut model_set = LitSet::new();
+                model_set.reserve(self.ts.max_latch);
+                for l in model.iter() {
+                    model_set.insert(*l);
+                }
+                cube.retain(|l| model_set.has(*l));
+            }
+        }
+    }
+
+    fn mic(
+        &mut self,
+        frame: usize,
+        mut cube: Cube,
+        depth: usize,
+        config: &GeneralizeConfig,
+    ) -> Cube {
+        if config.activity_order {
+            self.activity.sort_by_activity(&mut cube, true);
+        }
+        let mut i = 0;
+        while i < cube.len() {
+            let mut removed = cube.clone();
+            removed.remove(i);
+            match self.ctg_down(frame, &removed, depth, config) {
+                Some(core) => cube = core,
+                None => i += 1,
+            }
+        }
+        cube
+    }
+
+    /// generalize a cube blocked in the frame by dropping literals (MIC with ctg),
+    /// the generalized cube is still blocked in the frame
+    pub fn generalize(&mut self, frame: usize, cube: Cube, config: &GeneralizeConfig) -> Cube {
+        self.mic(frame, cube, 0, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::load, GeneralizeConfig, GipSAT};
+    use logic_form::Cube;
+
+    /// latchs x and y initialized to zero swapping their values, latch z taking
+    /// an input and latch w taking z, w is bad
+    const SWAP: &str = "aag 5 1 4 1 0\n2\n4 6\n6 4\n8 2\n10 8\n10\n";
+
+    #[test]
+    fn mic_is_relatively_inductive() {
+        let ts = load("mic", SWAP);
+        let cube: Cube = ts.latchs.iter().map(|l| l.lit()).collect();
+        let mut gipsat = GipSAT::new(ts);
+        gipsat.extend();
+        gipsat.extend();
+        assert!(gipsat.inductive(1, &cube, true));
+        let core = gipsat.inductive_core();
+        let mic = gipsat.generalize(1, core, &GeneralizeConfig::default());
+        assert!(!mic.is_empty() && mic.len() <= cube.len());
+        assert!(mic.iter().all(|l| cube.contains(l)));
+        assert!(gipsat.inductive(1, &mic, true));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Frame, GipSAT, Sat, Solver, Unsat};
+use logic_form::{Cube, Lit};
+use satif::{SatResult, SatifSat};
+
+impl Solver {
+    fn solve_with_act(&mut self, assump: &[Lit], act: &[Lit]) -> SatResult<Sat, Unsat> {
+        self.new_round(Some(assump.iter().map(|l| l.var())), None, false);
+        self.statistic.num_solve += 1;
+        self.clean_leanrt();
+        self.simplify();
+        self.garbage_collect();
+        let mut assumption = Cube::from(act);
+        assumption.extend_from_slice(assump);
+        self.search_with_restart(&assumption)
+    }
+}
+
+impl GipSAT {
+    /// compute the maximal inductive subset of the candidate lemmas
+    pub fn houdini(&mut self, candidates: &[Cube]) -> Vec<Cube> {
+        let frame = Frame::default();
+        let mut solver = Solver::new(Some(0), &self.solver_ts, &frame);
+        let mut lemmas: Vec<(Cube, Lit)> = Vec::new();
+        for cube in candidates.iter() {
+            if self.ts.cube_subsume_init(cube) {
+                continue;
+            }
+            let act = solver.new_var().lit();
+            let mut clause = !cube;
+            clause.push(!act);
+            solver.add_lemma(&clause);
+            lemmas.push((cube.clone(), act));
+        }
+        loop {
+            let mut fixpoint = true;
+            let mut i = 0;
+            while i < lemmas.len() {
+                let act: Vec<Lit> = lemmas.iter().map(|(_, act)| *act).collect();
+                let assump = self.ts.cube_next(&lemmas[i].0);
+                match solver.solve_with_act(&assump, &act) {
+                    SatResult::Sat(sat) => {
+                        fixpoint = false;
+                        lemmas.retain(|(cube, _)| {
+                            !self
+                                .ts
+                                .cube_next(cube)
+                                .iter()
+                                .all(|l| sat.lit_value(*l) == Some(true))
+                        });
+                    }
+                    SatResult::Unsat(_) => i += 1,
+                }
+            }
+            if fixpoint {
+                break;
+            }
+        }
+        lemmas.into_iter().map(|(cube, _)| cube).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::load, GipSAT};
+    use logic_form::Cube;
+
+    /// latchs x and y initialized to zero swapping their values, latch z taking
+    /// an input and latch w taking z, w is bad
+    const SWAP: &str = "aag 5 1 4 1 0\n2\n4 6\n6 4\n8 2\n10 8\n10\n";
+
+    #[test]
+    fn houdini_returns_inductive_subset() {
+        let ts = load("houdini", SWAP);
+        let candidates: Vec<Cube> = ts.latchs.iter().map(|l| Cube::from([l.lit()])).collect();
+        let mut gipsat = GipSAT::new(ts);
+        let lemmas = gipsat.houdini(&candidates);
+        assert_eq!(lemmas, candidates[..2]);
+        for _ in 0..3 {
+            gipsat.extend();
+        }
+        for l in lemmas.iter() {
+            gipsat.add_lemma(1, l.clone());
+        }
+        for l in lemmas.iter() {
+            assert!(gipsat.inductive(2, l, false));
+        }
+    }
+}
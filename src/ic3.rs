@@ -0,0 +1,150 @@
+use crate::{GeneralizeConfig, GipSAT};
+use logic_form::{Clause, Cube};
+use std::{cmp::Ordering, collections::BinaryHeap, rc::Rc};
+use transys::Transys;
+
+/// a step of counterexample, the latchs of the state and the inputs applied in it
+#[derive(Clone, Debug, Default)]
+pub struct Step {
+    pub latchs: Cube,
+    pub inputs: Cube,
+}
+
+pub enum IC3Result {
+    Safe,
+    Unsafe(Vec<Step>),
+}
+
+struct Obligation {
+    frame: usize,
+    cube: Cube,
+    inputs: Cube,
+    next: Option<Rc<Obligation>>,
+}
+
+impl PartialEq for Obligation {
+    fn eq(&self, other: &Self) -> bool {
+        self.frame == other.frame
+    }
+}
+
+impl Eq for Obligation {}
+
+impl PartialOrd for Obligation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Obligation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.frame.cmp(&self.frame)
+    }
+}
+
+/// a plain IC3 driver on top of GipSAT
+pub struct IC3 {
+    ts: Rc<Transys>,
+    gipsat: GipSAT,
+    config: GeneralizeConfig,
+}
+
+impl IC3 {
+    pub fn new(ts: Rc<Transys>, config: GeneralizeConfig) -> Self {
+        let gipsat = GipSAT::new(ts.clone());
+        Self { ts, gipsat, config }
+    }
+
+    /// restrict the transition system by a clause, the frames stay valid so that
+    /// checking can continue on them
+    pub fn add_constraint(&mut self, clause: Clause) {
+        self.gipsat.add_constraint(clause);
+    }
+
+    fn inputs(&self) -> Cube {
+        let mut inputs = Cube::new();
+        for input in self.ts.inputs.iter() {
+            let lit = input.lit();
+            match self.gipsat.unblocked_value(lit) {
+                Some(true) => inputs.push(lit),
+                Some(false) => inputs.push(!lit),
+                None => (),
+            }
+        }
+        inputs
+    }
+
+    fn trace(mut po: Rc<Obligation>) -> Vec<Step> {
+        let mut trace = Vec::new();
+        loop {
+            trace.push(Step {
+                latchs: po.cube.clone(),
+                inputs: po.inputs.clone(),
+            });
+            match po.next.clone() {
+                Some(next) => po = next,
+                None => return trace,
+            }
+        }
+    }
+
+    fn block(&mut self, po: Obligation) -> Option<Vec<Step>> {
+        let mut queue = BinaryHeap::new();
+        queue.push(Rc::new(po));
+        while let Some(po) = queue.pop() {
+            if po.frame == 0 {
+                return Some(Self::trace(po));
+            }
+            if self.gipsat.inductive(po.frame, &po.cube, true) {
+                // the activity orders the literals dropped first in generalization
+                self.gipsat.bump_activity(&po.cube);
+                let core = self.gipsat.inductive_core();
+                let core = self.gipsat.generalize(po.frame, core, &self.config);
+                self.gipsat.add_lemma(po.frame, core);
+                if po.frame < self.gipsat.level() {
+                    queue.push(Rc::new(Obligation {
+                        frame: po.frame + 1,
+                        cube: po.cube.clone(),
+                        inputs: po.inputs.clone(),
+                        next: po.next.clone(),
+                    }));
+                }
+            } else {
+                let inputs = self.inputs();
+                let cube = self.gipsat.get_predecessor();
+                queue.push(Rc::new(Obligation {
+                    frame: po.frame - 1,
+                    cube,
+                    inputs,
+                    next: Some(po.clone()),
+                }));
+                queue.push(po);
+            }
+        }
+        None
+    }
+
+    pub fn check(&mut self) -> IC3Result {
+        self.gipsat.extend();
+        loop {
+            while self.gipsat.has_bad() {
+                let inputs = self.inputs();
+                let cube = self.gipsat.get_predecessor();
+                let po = Obligation {
+                    frame: self.gipsat.level(),
+                    cube,
+                    inputs,
+                    next: None,
+                };
+                if let Some(trace) = self.block(po) {
+                    return IC3Result::Unsafe(trace);
+                }
+            }
+            self.gipsat.extend();
+            self.gipsat.decay_activity();
+            if self.gipsat.propagate() {
+                return IC3Result::Safe;
+            }
+        }
+    }
+}
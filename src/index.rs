@@ -0,0 +1,203 @@
+use logic_form::{Lemma, Lit, LitMap, Var};
+
+/// the frame of the lemmas in the infinite frame
+pub const INF_FRAME: usize = usize::MAX;
+
+/// an occurrence index of the lemmas of all frames answering subsumption queries
+/// without scanning every lemma. each lemma is in the occurrence lists of all its
+/// literals and watched by its literal occurring least at insertion, which keeps
+/// the watch lists short on literals shared by many lemmas. a lemma subsuming a
+/// cube must have its watched literal in the cube and a lemma subsumed by a cube must occur in the
+/// occurrence list of every literal of the cube. removed lemmas are left in the
+/// lists and dropped when they outnumber the alive ones, their ids are reused
+/// afterwards.
+#[derive(Default)]
+pub struct LemmaIndex {
+    lemmas: Vec<Option<(usize, Lemma)>>,
+    occurs: LitMap<Vec<usize>>,
+    watches: LitMap<Vec<usize>>,
+    num_alive: usize,
+    removed: Vec<usize>,
+    free: Vec<usize>,
+}
+
+impl LemmaIndex {
+    #[inline]
+    pub fn reserve(&mut self, var: Var) {
+        self.occurs.reserve(var);
+        self.watches.reserve(var);
+    }
+
+    /// index the lemma in the frame, returns the id of the lemma
+    pub fn insert(&mut self, frame: usize, lemma: &Lemma) -> usize {
+        let id = match self.free.pop() {
+            Some(id) => id,
+            None => {
+                self.lemmas.push(None);
+                self.lemmas.len() - 1
+            }
+        };
+        let watch = *lemma.iter().min_by_key(|l| self.occurs[**l].len()).unwrap();
+        for l in lemma.iter() {
+            self.occurs[*l].push(id);
+        }
+        self.watches[watch].push(id);
+        self.lemmas[id] = Some((frame, lemma.clone()));
+        self.num_alive += 1;
+        id
+    }
+
+    pub fn remove(&mut self, id: usize) {
+        assert!(self.lemmas[id].take().is_some());
+        self.num_alive -= 1;
+        self.removed.push(id);
+        if self.removed.len() > self.num_alive {
+            self.compact();
+        }
+    }
+
+    #[inline]
+    pub fn set_frame(&mut self, id: usize, frame: usize) {
+        self.lemmas[id].as_mut().unwrap().0 = frame;
+    }
+
+    /// get the frame of the lemma, none if it has been removed
+    #[inline]
+    pub fn frame(&self, id: usize) -> Option<usize> {
+        self.lemmas[id].as_ref().map(|(f, _)| *f)
+    }
+
+    #[inline]
+    pub fn lemma(&self, id: usize) -> &Lemma {
+        &self.lemmas[id].as_ref().unwrap().1
+    }
+
+    #[inline]
+    fn alive(&self, id: usize, filter: &impl Fn(usize) -> bool) -> Option<&Lemma> {
+        match &self.lemmas[id] {
+            Some((f, l)) if filter(*f) => Some(l),
+            _ => None,
+        }
+    }
+
+    /// find the lemmas subsuming the cube whose frame passes the filter, returns
+    /// pairs of id and frame
+    pub fn subsumers(&self, cube: &Lemma, filter: impl Fn(usize) -> bool) -> Vec<(usize, usize)> {
+        let mut res = Vec::new();
+        for lit in cube.iter() {
+            for id in self.watches[*lit].iter() {
+                if let Some(l) = self.alive(*id, &filter) {
+                    if l.subsume(cube) {
+                        res.push((*id, self.frame(*id).unwrap()));
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// find a lemma subsuming the cube whose frame passes the filter
+    pub fn find_subsumer(&self, cube: &Lemma, filter: impl Fn(usize) -> bool) -> Option<usize> {
+        for lit in cube.iter() {
+            for id in self.watches[*lit].iter() {
+                if let Some(l) = self.alive(*id, &filter) {
+                    if l.subsume(cube) {
+                        return Some(*id);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// find the lemmas subsumed by the cube whose frame passes the filter, returns
+    /// pairs of id and frame, none for the empty cube
+    pub fn subsumed(&self, cube: &Lemma, filter: impl Fn(usize) -> bool) -> Vec<(usize, usize)> {
+        let mut res = Vec::new();
+        let Some(lit) = cube.iter().min_by_key(|l| self.occurs[**l].len()) else {
+            return res;
+        };
+        let lit: Lit = *lit;
+        for id in self.occurs[lit].iter() {
+            if let Some(l) = self.alive(*id, &filter) {
+                if cube.subsume(l) {
+                    res.push((*id, self.frame(*id).unwrap()));
+                }
+            }
+        }
+        res
+    }
+
+    /// drop the removed lemmas from the lists, their ids are free for reuse
+    fn compact(&mut self) {
+        let lemmas = &self.lemmas;
+        for occurs in self.occurs.iter_mut().chain(self.watches.iter_mut()) {
+            occurs.retain(|id| lemmas[*id].is_some());
+        }
+        self.free.append(&mut self.removed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LemmaIndex;
+    use logic_form::{Cube, Lemma, Lit, Var};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const NUM_VAR: usize = 8;
+
+    fn random_lemma(rng: &mut StdRng) -> Lemma {
+        let len = rng.gen_range(1..=4);
+        let mut vars: Vec<usize> = (1..=NUM_VAR).collect();
+        let mut cube = Cube::new();
+        for _ in 0..len {
+            let v = vars.swap_remove(rng.gen_range(0..vars.len()));
+            cube.push(Lit::new(Var::new(v), rng.gen()));
+        }
+        Lemma::new(cube)
+    }
+
+    /// the answers of the index agree with a linear scan over the alive lemmas
+    #[test]
+    fn index_agrees_with_linear_scan() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut index = LemmaIndex::default();
+        index.reserve(Var::new(NUM_VAR));
+        let mut alive: Vec<(usize, usize, Lemma)> = Vec::new();
+        for round in 0..2000 {
+            if !alive.is_empty() && rng.gen_bool(0.3) {
+                let (id, ..) = alive.swap_remove(rng.gen_range(0..alive.len()));
+                index.remove(id);
+            } else {
+                let frame = rng.gen_range(1..5);
+                let lemma = random_lemma(&mut rng);
+                let id = index.insert(frame, &lemma);
+                alive.push((id, frame, lemma));
+            }
+            let cube = random_lemma(&mut rng);
+            let filter = |f: usize| f >= round % 4;
+            let mut subsumers = index.subsumers(&cube, filter);
+            subsumers.sort();
+            let mut expected: Vec<_> = alive
+                .iter()
+                .filter(|(_, f, l)| filter(*f) && l.subsume(&cube))
+                .map(|(id, f, _)| (*id, *f))
+                .collect();
+            expected.sort();
+            assert_eq!(subsumers, expected);
+            match index.find_subsumer(&cube, filter) {
+                Some(id) => assert!(expected.iter().any(|(e, _)| *e == id)),
+                None => assert!(expected.is_empty()),
+            }
+            let mut subsumed = index.subsumed(&cube, filter);
+            subsumed.sort();
+            let mut expected: Vec<_> = alive
+                .iter()
+                .filter(|(_, f, l)| filter(*f) && cube.subsume(l))
+                .map(|(id, f, _)| (*id, *f))
+                .collect();
+            expected.sort();
+            assert_eq!(subsumed, expected);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{index::INF_FRAME, Call, GipSAT};
+use logic_form::{Clause, Cube};
+
+impl GipSAT {
+    /// add a clause permanently to all current and future solvers including the
+    /// lift solver, it restricts the transition relation
+    pub fn add_constraint(&mut self, clause: Clause) {
+        let call = Call::AddConstraint(clause.clone());
+        self.recorded(|gipsat| gipsat.add_constraint_inner(clause), |_| call)
+    }
+
+    fn add_constraint_inner(&mut self, clause: Clause) {
+        for s in self.solvers.iter_mut().chain(Some(&mut self.lift)) {
+            s.add_constrain(&clause);
+            if clause.len() == 1 {
+                s.simplify_satisfied();
+            }
+        }
+        self.invariants.push(clause);
+    }
+
+    /// move a lemma known to be an invariant to the infinite frame, the lemmas of
+    /// the frames it subsumes are removed and its clause is added permanently to
+    /// all current and future solvers including the lift solver
+    pub fn add_inf_lemma(&mut self, lemma: Cube) {
+        let call = Call::AddInfLemma(lemma.clone());
+        self.recorded(|gipsat| gipsat.add_inf_lemma_inner(lemma), |_| call)
+    }
+
+    fn add_inf_lemma_inner(&mut self, lemma: Cube) {
+        let lemma = logic_form::Lemma::new(lemma);
+        if self
+            .index
+            .find_subsumer(&lemma, |f| f == INF_FRAME)
+            .is_some()
+        {
+            return;
+        }
+        assert!(!self.ts.cube_subsume_init(lemma.cube()));
+        for (id, i) in self.index.subsumed(&lemma, |f| f > 0) {
+            if i == INF_FRAME {
+                self.index.remove(id);
+                continue;
+            }
+            let l = self.detach_lemma(i, id);
+            self.observer.lemma_subsumed(i, l.cube());
+        }
+        self.frame.inf_mut().retain(|l| !lemma.subsume(l));
+        let clause: Clause = !lemma.cube();
+        for s in self.solvers.iter_mut().chain(Some(&mut self.lift)) {
+            s.add_constrain(&clause);
+            if clause.len() == 1 {
+                s.simplify_satisfied();
+            }
+        }
+        self.invariants.push(clause);
+        self.index.insert(INF_FRAME, &lemma);
+        self.frame.inf_mut().push(lemma);
+    }
+
+    /// promote the lemmas of the frames above an empty frame to the infinite frame,
+    /// they form an inductive invariant, returns the number of promoted lemmas
+    pub fn promote_invariants(&mut self) -> usize {
+        self.recorded(Self::promote_invariants_inner, |res| {
+            Call::PromoteInvariants(*res)
+        })
+    }
+
+    fn promote_invariants_inner(&mut self) -> usize {
+        let Some(empty) = (1..self.level()).find(|i| self.frame[*i].is_empty()) else {
+            return 0;
+        };
+        let mut lemmas = Vec::new();
+        for i in empty + 1..self.frame.len() {
+            lemmas.extend(self.frame[i].iter().map(|l| l.cube().clone()));
+        }
+        let num = lemmas.len();
+        for l in lemmas {
+            self.add_inf_lemma(l);
+        }
+        num
+    }
+}
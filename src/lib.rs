@@ -1,36 +1,79 @@
 #![feature(get_mut_unchecked, c_size_t)]
 
+mod abstraction;
 mod analyze;
 mod cdb;
+#[cfg(feature = "check_domain")]
+mod check;
 mod cif;
+mod coi;
+mod dimacs;
 mod domain;
+mod frames;
+mod generalize;
+mod houdini;
+mod ic3;
+mod index;
+mod infinity;
+mod liveness;
+mod minimize;
+mod observer;
 mod propagate;
+mod property;
+mod record;
+mod recycle;
 mod search;
+mod share;
+mod shared;
 mod simplify;
 mod statistic;
+mod ternary;
+#[cfg(test)]
+mod testing;
 mod utils;
 mod vsids;
+mod weaken;
+
+pub use abstraction::Abstraction;
+pub use frames::LemmaOrigin;
+pub use generalize::GeneralizeConfig;
+pub use ic3::{IC3Result, Step, IC3};
+pub use liveness::{Lasso, LassoError, Liveness, LivenessResult};
+pub use minimize::CoreMinimize;
+pub use observer::{JsonLogger, NoObserver, Observer, Query};
+pub use property::PropertyStatus;
+pub use record::{record_aig_rst, replay, Call, Divergence};
+pub use recycle::RecyclePolicy;
+pub use share::ShareConfig;
+pub use statistic::{FrameStatistic, Statistics};
 
 use crate::utils::Lbool;
 use analyze::Analyze;
 use cdb::{CRef, ClauseDB, ClauseKind, CREF_NONE};
+use coi::coi_reduce;
 use domain::Domain;
 use giputils::gvec::Gvec;
+use index::LemmaIndex;
 use logic_form::{Clause, Cube, Lit, LitSet, Var, VarMap};
 use propagate::Watchers;
+use property::Property;
 use rand::{rngs::StdRng, SeedableRng};
+use record::Recorder;
 use satif::{SatResult, SatifSat, SatifUnsat};
 use search::Value;
+use share::Share;
 use simplify::Simplify;
 use statistic::{GipSATStatistic, SolverStatistic};
 use std::{
+    collections::HashMap,
     mem::take,
     ops::{Deref, DerefMut},
     rc::Rc,
     time::Instant,
 };
+use ternary::TernarySim;
 use transys::Transys;
-use vsids::Vsids;
+use vsids::{Activity, Vsids};
 
 pub struct Solver {
     id: Option<usize>,
@@ -53,9 +96,18 @@ pub struct Solver {
 
     ts: Rc<Transys>,
     frame: Frame,
+    observer: Rc<dyn Observer>,
 
     rng: StdRng,
     statistic: SolverStatistic,
+    recycle_solve: usize,
+    /// a unit learnt not only from the transition relation is assigned at level
+    /// zero, the assignments of level zero may depend on lemmas
+    lemma_unit: bool,
+    /// an imported shared clause conflicts at level zero, every query is unsat
+    /// until the solver is rebuilt
+    root_conflict: bool,
+    share: Share,
 }
 
 impl Solver {
@@ -64,6 +116,7 @@ impl Solver {
             id,
             ts: ts.clone(),
             frame: frame.clone(),
+            observer: Rc::new(NoObserver),
             cdb: Default::default(),
             watchers: Default::default(),
             value: Default::default(),
@@ -82,6 +135,10 @@ impl Solver {
             statistic: Default::default(),
             constrain_act: None,
             rng: StdRng::seed_from_u64(0),
+            recycle_solve: 0,
+            lemma_unit: false,
+            root_conflict: false,
+            share: Default::default(),
         };
         while solver.num_var() < solver.ts.num_var {
             solver.new_var();
@@ -89,6 +146,7 @@ impl Solver {
         for cls in ts.trans.iter() {
             solver.add_clause_inner(cls, ClauseKind::Trans);
         }
+        solver.cdb.mark_trans_only();
         if id.is_some() {
             for c in ts.constraints.iter() {
                 solver.add_clause_inner(&[*c], ClauseKind::Trans);
@@ -103,6 +161,16 @@ impl Solver {
         solver
     }
 
+    /// precompute the cone of every next state latch variable, so that setting up
+    /// the domain of a query only merges cached cones
+    pub fn precompute_cone(&mut self) {
+        let start = Instant::now();
+        let ts = self.ts.clone();
+        let roots = ts.latchs.iter().map(|l| ts.lit_next(l.lit()).var());
+        self.statistic.cone_cache_size = self.domain.precompute_cone(roots, &ts);
+        self.statistic.cone_precompute_time += start.elapsed();
+    }
+
     pub fn new_var(&mut self) -> Var {
         let var = Var::new(self.num_var());
         self.value.reserve(var);
@@ -123,6 +191,14 @@ impl Solver {
     }
 
     fn simplify_clause(&mut self, cls: &[Lit]) -> Option<logic_form::Clause> {
+        let clause = self.reduce_clause(cls)?;
+        assert!(!clause.is_empty());
+        Some(clause)
+    }
+
+    /// remove the literals false at level zero, none if the clause is satisfied,
+    /// an empty clause conflicts at level zero
+    fn reduce_clause(&mut self, cls: &[Lit]) -> Option<logic_form::Clause> {
         assert!(self.highest_level() == 0);
         let mut clause = logic_form::Clause::new();
         for l in cls.iter() {
@@ -135,11 +211,13 @@ impl Solver {
                 _ => clause.push(*l),
             }
         }
-        assert!(!clause.is_empty());
         Some(clause)
     }
 
     fn add_clause_inner(&mut self, clause: &[Lit], mut kind: ClauseKind) -> CRef {
+        if self.root_conflict {
+            return CREF_NONE;
+        }
         let clause = match self.simplify_clause(clause) {
             Some(clause) => clause,
             None => return CREF_NONE,
@@ -176,6 +254,16 @@ impl Solver {
         self.add_clause_inner(lemma, ClauseKind::Lemma)
     }
 
+    fn add_constrain(&mut self, constrain: &[Lit]) {
+        self.backtrack(0, false);
+        self.clean_temporary();
+        if self.id.is_some() {
+            self.domain
+                .add_constrain(constrain.iter().map(|l| l.var()), &self.ts, &self.value);
+        }
+        self.add_clause_inner(constrain, ClauseKind::Trans);
+    }
+
     #[inline]
     fn remove_lemma(&mut self, cref: CRef) {
         self.backtrack(0, false);
@@ -193,6 +281,7 @@ impl Solver {
     ) {
         self.backtrack(0, self.temporary_domain);
         self.clean_temporary();
+        self.import_shared();
         // dbg!(&self.name);
         // self.vsids.activity.print();
         // dbg!(self.num_var());
@@ -210,7 +299,10 @@ impl Solver {
 
         if !self.temporary_domain {
             if let Some(domain) = domain {
+                let start = Instant::now();
                 self.domain.enable_local(domain, &self.ts, &self.value);
+                self.statistic.domain_time += start.elapsed();
+                self.statistic.cone_cache_size = self.domain.cone_cache_size();
                 if self.constrain_act.is_some() {
                     assert!(!self.domain.local.has(self.constrain_act.unwrap().var()));
                     self.domain.local.insert(self.constrain_act.unwrap().var());
@@ -242,7 +334,10 @@ impl Solver {
         self.clean_leanrt();
         self.simplify();
         self.garbage_collect();
-        self.search_with_restart(assump)
+        let res = self.search_with_restart(assump);
+        #[cfg(feature = "check_domain")]
+        self.check_domain(&res, assump);
+        res
     }
 
     pub fn solve_with_constrain(
@@ -273,7 +368,10 @@ impl Solver {
         self.clean_leanrt();
         self.simplify();
         self.garbage_collect();
-        self.search_with_restart(&assumption)
+        let res = self.search_with_restart(&assumption);
+        #[cfg(feature = "check_domain")]
+        self.check_domain(&res, &assumption);
+        res
     }
 
     pub fn set_domain(&mut self, domain: impl Iterator<Item = Lit>) {
@@ -294,6 +392,115 @@ impl Solver {
     pub fn unset_domain(&mut self) {
         self.temporary_domain = false;
     }
+
+    /// decide the literal and propagate, the decision is undone on a conflict
+    fn try_decide(&mut self, lit: Lit) -> bool {
+        let trail_len = self.trail.len();
+        self.pos_in_trail.push(self.trail.len());
+        self.assign(lit, CREF_NONE);
+        if self.propagate() == CREF_NONE {
+            return true;
+        }
+        while self.trail.len() > trail_len {
+            let l = self.trail.pop().unwrap();
+            self.value.set_none(l.var());
+        }
+        self.pos_in_trail.pop();
+        self.propagated = trail_len;
+        false
+    }
+
+    /// extend the satisfying assignment to all variables of the transition system
+    /// by an unrestricted propagation and decision pass, a decision conflicting
+    /// with the clauses outside the domain is flipped. if the greedy pass fails,
+    /// a search over all variables assuming the domain assignment decides it.
+    /// returns none if the assignment can not be extended, the solver state is
+    /// restored afterwards
+    pub fn complete_model(&mut self) -> Option<Cube> {
+        let ts = self.ts.clone();
+        let trail_len = self.trail.len();
+        let level = self.highest_level();
+        let domain_len = self.domain.local.len();
+        for v in 0..ts.num_var {
+            let v = Var::new(v);
+            if !self.domain.has(v) {
+                self.domain.local.insert(v);
+            }
+        }
+        self.propagated = 0;
+        let mut complete = self.propagate() == CREF_NONE;
+        let vars = ts.inputs.iter().chain(ts.latchs.iter()).copied();
+        for v in vars.chain((0..ts.num_var).map(Var::new)) {
+            if !complete {
+                break;
+            }
+            if self.value.v(v.lit()).is_none() {
+                let decide = Lit::new(v, self.phase_saving[v].is_true());
+                complete = self.try_decide(decide) || self.try_decide(!decide);
+            }
+        }
+        let mut model = complete.then(|| self.full_assignment());
+        while self.trail.len() > trail_len {
+            let l = self.trail.pop().unwrap();
+            self.value.set_none(l.var());
+        }
+        self.pos_in_trail.truncate(level);
+        self.propagated = trail_len;
+        if model.is_none() {
+            model = self.search_model();
+        }
+        while self.domain.local.len() > domain_len {
+            let v = self.domain.local.set.pop().unwrap();
+            self.domain.local.has[v] = false;
+        }
+        model
+    }
+
+    fn full_assignment(&self) -> Cube {
+        (0..self.ts.num_var)
+            .map(|v| {
+                let v = Var::new(v);
+                Lit::new(v, self.value.v(v.lit()).is_true())
+            })
+            .collect()
+    }
+
+    /// search a model over all variables assuming the current assignment above
+    /// level zero, the assignment is restored afterwards. the units learnt by the
+    /// search are dropped with it, which loses no soundness
+    fn search_model(&mut self) -> Option<Cube> {
+        let root = self
+            .pos_in_trail
+            .first()
+            .copied()
+            .unwrap_or(self.trail.len());
+        let assump: Vec<Lit> = (root..self.trail.len()).map(|i| self.trail[i]).collect();
+        let pos_in_trail = self.pos_in_trail.clone();
+        self.backtrack(0, false);
+        for v in 0..self.ts.num_var {
+            let v = Var::new(v);
+            if self.value.v(v.lit()).is_none() {
+                self.vsids.push(v);
+            }
+        }
+        let model = (self.search(&assump, None) == Some(true)).then(|| self.full_assignment());
+        self.backtrack(0, false);
+        while self.trail.len() > root {
+            let l = self.trail.pop().unwrap();
+            self.value.set_none(l.var());
+        }
+        self.propagated = root;
+        let mut levels = pos_in_trail.iter().peekable();
+        for (i, l) in assump.into_iter().enumerate() {
+            while levels.next_if(|p| **p == root + i as u32).is_some() {
+                self.pos_in_trail.push(self.trail.len());
+            }
+            self.assign(l, CREF_NONE);
+        }
+        self.pos_in_trail.extend(levels);
+        self.propagated = self.trail.len();
+        model
+    }
 }
 
 pub struct Sat {
@@ -312,6 +519,16 @@ impl SatifSat for Sat {
     }
 }
 
+impl Sat {
+    /// get the values of all variables of the transition system, none if the
+    /// assignment can not be completed
+    #[inline]
+    pub fn complete_model(&self) -> Option<Cube> {
+        let solver = unsafe { &mut *self.solver };
+        solver.complete_model()
+    }
+}
+
 pub struct Unsat {
     solver: *mut Solver,
 }
@@ -333,6 +550,8 @@ pub struct BlockResultYes {
     pub unsat: Unsat,
     pub cube: Cube,
     pub assumption: Cube,
+    frame: usize,
+    strengthen: bool,
 }
 
 pub struct BlockResultNo {
@@ -352,6 +571,7 @@ pub struct Lemma {
     pub lemma: logic_form::Lemma,
     begin: usize,
     cref: Vec<CRef>,
+    id: usize,
 }
 
 impl Deref for Lemma {
@@ -383,6 +603,7 @@ impl Lemma {
 #[derive(Clone, Default)]
 pub struct Frame {
     frames: Rc<Vec<Vec<Lemma>>>,
+    inf: Rc<Vec<logic_form::Lemma>>,
 }
 
 impl Deref for Frame {
@@ -406,38 +627,135 @@ impl Frame {
     pub fn get_mut(&mut self) -> &mut Vec<Vec<Lemma>> {
         unsafe { Rc::get_mut_unchecked(&mut self.frames) }
     }
+
+    /// get the lemmas of the infinite frame, which are invariants
+    #[inline]
+    pub fn inf(&self) -> &[logic_form::Lemma] {
+        &self.inf
+    }
+
+    #[inline]
+    fn inf_mut(&mut self) -> &mut Vec<logic_form::Lemma> {
+        unsafe { Rc::get_mut_unchecked(&mut self.inf) }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LiftMode {
+    /// lift with the unsat core of the lift solver
+    #[default]
+    Sat,
+    /// lift with ternary simulation, falls back to sat lifting on failure
+    Ternary,
 }
 
 pub struct GipSAT {
     ts: Rc<Transys>,
+    solver_ts: Rc<Transys>,
     pub frame: Frame,
     solvers: Vec<Solver>,
     lift: Solver,
-    tmp_lit_set: LitSet,
+    /// single solver mode, the lemmas of each frame are guarded by its activation
+    /// literal in one solver
+    shared: bool,
+    act: Vec<Lit>,
+    ternary: TernarySim,
+    index: LemmaIndex,
+    activity: Activity,
     early: usize,
+    precompute_cone: bool,
+    /// the rst argument of Transys::from_aig the transition system was derived
+    /// with, written in the record header
+    aig_rst: Option<bool>,
+    recorder: Option<Recorder>,
+    observer: Rc<dyn Observer>,
+    recycle: Option<RecyclePolicy>,
+    share: Option<ShareConfig>,
     last_ind: Option<BlockResult>,
+    properties: Vec<Property>,
+    invariants: Vec<Clause>,
+    /// names of the variables given by the caller, the transition system carries
+    /// none
+    names: HashMap<Var, String>,
     statistic: GipSATStatistic,
 }
 
 impl GipSAT {
     /// create a new GipSAT instance from a transition system
     pub fn new(ts: Rc<Transys>) -> Self {
-        let mut tmp_lit_set = LitSet::new();
-        tmp_lit_set.reserve(ts.max_latch);
+        Self::new_inner(ts.clone(), ts, false)
+    }
+
+    /// create a new GipSAT instance whose solvers only load the transition relation
+    /// in the cone of influence of bad and constraints
+    pub fn new_with_coi(ts: Rc<Transys>) -> Self {
+        let solver_ts = Rc::new(coi_reduce(&ts));
+        Self::new_inner(ts, solver_ts, false)
+    }
+
+    fn new_inner(ts: Rc<Transys>, solver_ts: Rc<Transys>, shared: bool) -> Self {
+        let mut index = LemmaIndex::default();
+        index.reserve(ts.max_latch);
+        let mut activity = Activity::default();
+        activity.reserve(ts.max_latch);
         let frame = Default::default();
-        let lift = Solver::new(None, &ts, &frame);
+        let lift = Solver::new(None, &solver_ts, &frame);
+        let ternary = TernarySim::new(&ts);
+        let properties = vec![Property::new(ts.bad.clone())];
         Self {
             ts,
+            solver_ts,
             frame,
             solvers: Default::default(),
             lift,
-            tmp_lit_set,
+            shared,
+            act: Vec::new(),
+            ternary,
+            index,
+            activity,
             early: 1,
+            precompute_cone: false,
+            aig_rst: None,
+            recorder: None,
+            observer: Rc::new(NoObserver),
+            recycle: None,
+            share: None,
             last_ind: None,
+            properties,
+            invariants: Default::default(),
+            names: Default::default(),
             statistic: Default::default(),
         }
     }
 
+    /// precompute the cones of next state latchs in the solvers of new frames, the
+    /// cost is reported as cone_precompute_time and cone_cache_size in statistic
+    #[inline]
+    pub fn set_precompute_cone(&mut self, enable: bool) {
+        self.recorded(
+            |gipsat| gipsat.precompute_cone = enable,
+            |_| Call::SetPrecomputeCone(enable),
+        )
+    }
+
+    /// tell how the transition system was derived from the aiger model, the
+    /// rst argument of Transys::from_aig, so that a record can be replayed on the
+    /// same transition system
+    #[inline]
+    pub fn set_aig_rst(&mut self, rst: bool) {
+        self.aig_rst = Some(rst);
+    }
+
+    /// set the observer notified on frame, lemma and query events of GipSAT and
+    /// its solvers
+    pub fn set_observer(&mut self, observer: Rc<dyn Observer>) {
+        for s in self.solvers.iter_mut() {
+            s.observer = observer.clone();
+        }
+        self.lift.observer = observer.clone();
+        self.observer = observer;
+    }
+
     /// get the highest level of GipSAT
     #[inline]
     pub fn level(&self) -> usize {
@@ -447,9 +765,33 @@ impl GipSAT {
     /// extend with a new frame    
     #[inline]
     pub fn extend(&mut self) {
-        self.solvers
-            .push(Solver::new(Some(self.frame.len()), &self.ts, &self.frame));
+        self.recorded(Self::extend_inner, |_| Call::Extend)
+    }
+
+    fn new_solver(&self, id: usize) -> Solver {
+        let mut solver = Solver::new(Some(id), &self.solver_ts, &self.frame);
+        solver.observer = self.observer.clone();
+        solver.share.config = self.share;
+        if self.precompute_cone {
+            solver.precompute_cone();
+        }
+        for c in self.invariants.iter() {
+            solver.add_constrain(c);
+        }
+        solver
+    }
+
+    fn extend_inner(&mut self) {
+        if !self.shared || self.solvers.is_empty() {
+            let solver = self.new_solver(self.frame.len());
+            self.solvers.push(solver);
+        }
+        if self.shared {
+            let act = self.solvers[0].new_var().lit();
+            self.act.push(act);
+        }
         self.frame.push(Vec::new());
+        self.observer.frame_extended(self.level());
         if self.level() == 0 {
             for cube in self.ts.inits() {
                 self.add_lemma(0, cube)
@@ -459,32 +801,67 @@ impl GipSAT {
 
     #[inline]
     pub fn trivial_contained(&mut self, frame: usize, lemma: &logic_form::Lemma) -> bool {
-        for l in lemma.iter() {
-            self.tmp_lit_set.insert(*l);
-        }
-        for i in frame..self.frame.len() {
-            for l in self.frame[i].iter() {
-                if l.subsume_set(lemma, &self.tmp_lit_set) {
-                    self.tmp_lit_set.clear();
-                    return true;
-                }
+        self.index.find_subsumer(lemma, |f| f >= frame).is_some()
+    }
+
+    /// remove the indexed lemma from the frame and detach its clauses from the solvers
+    fn detach_lemma(&mut self, frame: usize, id: usize) -> Lemma {
+        let j = self.frame[frame].iter().position(|l| l.id == id).unwrap();
+        let l = self.frame[frame].swap_remove(j);
+        for (k, cref) in (l.begin..).zip(l.cref.iter()) {
+            if *cref != CREF_NONE {
+                self.solvers[k].remove_lemma(*cref);
             }
         }
-        self.tmp_lit_set.clear();
-        false
+        self.index.remove(id);
+        l
+    }
+
+    /// add the clause of the lemma of the frame to the solvers from begin to the
+    /// frame, in single solver mode the clause is guarded by the activation literal
+    /// of the frame instead, returns the first solver holding the clause and the
+    /// clauses
+    fn attach_lemma(
+        &mut self,
+        frame: usize,
+        begin: usize,
+        lemma: &logic_form::Lemma,
+    ) -> (usize, Vec<CRef>) {
+        let mut clause = !lemma.cube();
+        if self.shared {
+            clause.push(!self.act[frame]);
+            return (0, vec![self.solvers[0].add_lemma(&clause)]);
+        }
+        let cref = (begin..=frame)
+            .map(|k| self.solvers[k].add_lemma(&clause))
+            .collect();
+        (begin, cref)
     }
 
     /// add a lemma to frame
     #[inline]
     pub fn add_lemma(&mut self, frame: usize, lemma: Cube) {
+        let call = Call::AddLemma(frame, lemma.clone());
+        self.recorded(|gipsat| gipsat.add_lemma_inner(frame, lemma), |_| call)
+    }
+
+    fn add_lemma_inner(&mut self, frame: usize, lemma: Cube) {
         let lemma = logic_form::Lemma::new(lemma);
         if frame == 0 {
             assert!(self.frame.len() == 1);
-            assert!(self.solvers[0].add_lemma(&!lemma.cube()) == CREF_NONE);
+            let (begin, cref) = if self.shared {
+                self.attach_lemma(0, 0, &lemma)
+            } else {
+                assert!(self.solvers[0].add_lemma(&!lemma.cube()) == CREF_NONE);
+                (1, Vec::new())
+            };
+            self.observer.lemma_added(0, lemma.cube());
+            let id = self.index.insert(0, &lemma);
             self.frame[0].push(Lemma {
                 lemma,
-                cref: Vec::new(),
-                begin: 1,
+                cref,
+                begin,
+                id,
             });
             return;
         }
@@ -492,74 +869,111 @@ impl GipSAT {
             return;
         }
         assert!(!self.ts.cube_subsume_init(lemma.cube()));
-        let mut begin = None;
-        'fl: for i in (1..=frame).rev() {
-            let mut j = 0;
-            while j < self.frame[i].len() {
-                let l = &self.frame[i][j];
-                if begin.is_none() && l.subsume(&lemma) {
-                    if l.eq(&lemma) {
-                        let mut eq_lemma = self.frame[i].swap_remove(j);
-                        let clause = !lemma.cube();
-                        for k in i + 1..=frame {
-                            eq_lemma.cref.push(self.solvers[k].add_lemma(&clause));
-                        }
-                        self.frame[frame].push(eq_lemma);
-                        self.early = self.early.min(i + 1);
-                        return;
-                    } else {
-                        begin = Some(i + 1);
-                        break 'fl;
-                    }
-                }
-                if lemma.subsume(l) {
-                    for k in l.begin..=i {
-                        let cref = l.get_cref(k);
-                        if cref != CREF_NONE {
-                            self.solvers[k].remove_lemma(cref);
-                        }
-                    }
-                    self.frame[i].swap_remove(j);
-                    continue;
+        // the subsuming lemma of the highest lower frame, an equal one is preferred
+        let parent = self
+            .index
+            .subsumers(&lemma, |f| (1..frame).contains(&f))
+            .into_iter()
+            .max_by_key(|(id, f)| (*f, *self.index.lemma(*id) == lemma));
+        let low = parent.map_or(0, |(_, f)| f);
+        for (id, i) in self.index.subsumed(&lemma, |f| f > low && f <= frame) {
+            let l = self.detach_lemma(i, id);
+            self.observer.lemma_subsumed(i, l.cube());
+        }
+        let mut pushed = None;
+        if let Some((id, i)) = parent.filter(|(id, _)| *self.index.lemma(*id) == lemma) {
+            if self.shared {
+                // the guard of the clause changes with the frame
+                self.detach_lemma(i, id);
+                pushed = Some(i);
+            } else {
+                let j = self.frame[i].iter().position(|l| l.id == id).unwrap();
+                let mut eq_lemma = self.frame[i].swap_remove(j);
+                let clause = !lemma.cube();
+                for k in i + 1..=frame {
+                    eq_lemma.cref.push(self.solvers[k].add_lemma(&clause));
                 }
-                j += 1;
+                self.observer.lemma_pushed(i, frame, lemma.cube());
+                self.index.set_frame(id, frame);
+                self.frame[frame].push(eq_lemma);
+                self.early = self.early.min(i + 1);
+                return;
             }
         }
-        let clause = !lemma.cube();
-        let begin = begin.unwrap_or(1);
-        let mut cref = Vec::new();
-        for i in begin..=frame {
-            cref.push(self.solvers[i].add_lemma(&clause))
+        let begin = parent.map_or(1, |(_, i)| i + 1);
+        let (first, cref) = self.attach_lemma(frame, begin, &lemma);
+        match pushed {
+            Some(i) => self.observer.lemma_pushed(i, frame, lemma.cube()),
+            None => self.observer.lemma_added(frame, lemma.cube()),
         }
-        self.frame[frame].push(Lemma { lemma, cref, begin });
+        let id = self.index.insert(frame, &lemma);
+        self.frame[frame].push(Lemma {
+            lemma,
+            cref,
+            begin: first,
+            id,
+        });
         self.early = self.early.min(begin);
     }
 
-    pub fn parent_lemma(&self, cube: &Cube, frame: usize) -> Vec<logic_form::Lemma> {
-        let lemma = logic_form::Lemma::new(cube.clone());
-        let mut res = Vec::new();
-        if frame == 1 {
-            return res;
-        }
-        for c in self.frame[frame - 1].iter() {
-            if c.subsume(&lemma) {
-                res.push(c.lemma.clone());
+    /// import candidate lemmas, each candidate is added to the highest frame
+    /// it is inductive relative to, returns the accepted frame of each candidate
+    pub fn import_lemmas(&mut self, candidates: &[Cube]) -> Vec<Option<usize>> {
+        let mut res = Vec::with_capacity(candidates.len());
+        for cube in candidates.iter() {
+            if self.ts.cube_subsume_init(cube) {
+                res.push(None);
+                continue;
+            }
+            let mut frame = 0;
+            while frame < self.level() && self.inductive(frame + 1, cube, true) {
+                frame += 1;
+            }
+            self.last_ind = None;
+            if frame == 0 {
+                res.push(None);
+                continue;
             }
+            self.add_lemma(frame, cube.clone());
+            res.push(Some(frame));
         }
         res
     }
 
+    pub fn parent_lemma(&self, cube: &Cube, frame: usize) -> Vec<logic_form::Lemma> {
+        if frame == 1 {
+            return Vec::new();
+        }
+        let lemma = logic_form::Lemma::new(cube.clone());
+        self.index
+            .subsumers(&lemma, |f| f == frame - 1)
+            .into_iter()
+            .map(|(id, _)| self.index.lemma(id).clone())
+            .collect()
+    }
+
     /// query whether the cube is inductively relative to the frame
     pub fn inductive(&mut self, frame: usize, cube: &[Lit], strengthen: bool) -> bool {
+        self.recorded(
+            |gipsat| gipsat.inductive_inner(frame, cube, strengthen),
+            |res| Call::Inductive(frame, Cube::from(cube), strengthen, *res),
+        )
+    }
+
+    fn inductive_inner(&mut self, frame: usize, cube: &[Lit], strengthen: bool) -> bool {
         let start = Instant::now();
         self.statistic.num_sat += 1;
-        let solver_idx = frame - 1;
+        self.observer.query_started(Query::Inductive(frame));
+        let solver_idx = self.solver_of(frame - 1);
+        self.recycle_solver(solver_idx);
         let assumption = self.ts.cube_next(cube);
+        let mut assump = self.frame_act(frame - 1);
+        assump.extend_from_slice(&assumption);
         let res = if strengthen {
             let constrain = Clause::from_iter(cube.iter().map(|l| !*l));
-            self.solvers[solver_idx].solve_with_constrain(&assumption, constrain, true)
+            self.solvers[solver_idx].solve_with_constrain(&assump, constrain, true)
         } else {
-            self.solvers[solver_idx].solve_with_domain(&assumption, true)
+            self.solvers[solver_idx].solve_with_domain(&assump, true)
         };
         self.last_ind = Some(match res {
             SatResult::Sat(sat) => BlockResult::No(BlockResultNo { sat, assumption }),
@@ -567,19 +981,56 @@ impl GipSAT {
                 unsat,
                 cube: Cube::from(cube),
                 assumption,
+                frame,
+                strengthen,
             }),
         });
         self.statistic.avg_sat_time += start.elapsed();
-        matches!(self.last_ind.as_ref().unwrap(), BlockResult::Yes(_))
+        self.statistic.inductive_time += start.elapsed();
+        self.exchange_learnt();
+        let res = matches!(self.last_ind.as_ref().unwrap(), BlockResult::Yes(_));
+        self.observer
+            .query_finished(Query::Inductive(frame), !res, start.elapsed());
+        res
     }
 
     /// get the inductive core
     pub fn inductive_core(&mut self) -> Cube {
+        self.inductive_core_with(None)
+    }
+
+    /// get the inductive core, minimized with the method using at most budget solves
+    pub fn minimal_inductive_core(&mut self, method: CoreMinimize, budget: usize) -> Cube {
+        self.inductive_core_with(Some((method, budget)))
+    }
+
+    fn inductive_core_with(&mut self, minimize: Option<(CoreMinimize, usize)>) -> Cube {
+        self.recorded(
+            |gipsat| gipsat.inductive_core_inner(minimize),
+            |res| Call::InductiveCore(minimize, res.clone()),
+        )
+    }
+
+    fn inductive_core_inner(&mut self, minimize: Option<(CoreMinimize, usize)>) -> Cube {
         let last_ind = take(&mut self.last_ind);
         let block = match last_ind.unwrap() {
             BlockResult::Yes(block) => block,
             BlockResult::No(_) => panic!(),
         };
+        if let Some((method, budget)) = minimize {
+            let core: Cube = self
+                .frame_act(block.frame - 1)
+                .iter()
+                .chain(block.assumption.iter())
+                .filter(|l| block.unsat.has(**l))
+                .copied()
+                .collect();
+            let constrain = block
+                .strengthen
+                .then(|| Clause::from_iter(block.cube.iter().map(|l| !*l)));
+            let solver_idx = self.solver_of(block.frame - 1);
+            self.solvers[solver_idx].minimize_core(&core, constrain, method, budget);
+        }
         let mut ans = Cube::new();
         for i in 0..block.cube.len() {
             if block.unsat.has(block.assumption[i]) {
@@ -616,17 +1067,44 @@ impl GipSAT {
         unblock.lit_value(lit)
     }
 
+    /// get the values of all variables of the transition system in the last
+    /// unblocked query, including internal signals. with coi reduction the values
+    /// outside the cone are simulated on the full transition system. returns none
+    /// if the assignment conflicts with clauses outside the query domain
+    pub fn complete_model(&mut self) -> Option<Cube> {
+        let unblock = match self.last_ind.as_ref().unwrap() {
+            BlockResult::Yes(_) => panic!(),
+            BlockResult::No(unblock) => unblock,
+        };
+        let mut model = unblock.sat.complete_model()?;
+        if !Rc::ptr_eq(&self.ts, &self.solver_ts) {
+            self.ternary.simulate_model(&mut model);
+        }
+        Some(model)
+    }
+
     /// get the predecessor
     pub fn get_predecessor(&mut self) -> Cube {
+        self.get_predecessor_with(LiftMode::Sat)
+    }
+
+    /// get the predecessor, lifting the latches with the given mode
+    pub fn get_predecessor_with(&mut self, mode: LiftMode) -> Cube {
+        self.recorded(
+            |gipsat| gipsat.get_predecessor_inner(mode),
+            |res| Call::Predecessor(mode, res.clone()),
+        )
+    }
+
+    fn get_predecessor_inner(&mut self, mode: LiftMode) -> Cube {
         let last_ind = take(&mut self.last_ind);
         let unblock = match last_ind.unwrap() {
             BlockResult::Yes(_) => panic!(),
             BlockResult::No(unblock) => unblock,
         };
         let mut assumption = Cube::new();
-        let mut cls = unblock.assumption.clone();
-        cls.extend_from_slice(&self.ts.constraints);
-        let cls = !cls;
+        let mut target = unblock.assumption.clone();
+        target.extend_from_slice(&self.ts.constraints);
         for input in self.ts.inputs.iter() {
             let lit = input.lit();
             match unblock.sat.lit_value(lit) {
@@ -646,21 +1124,49 @@ impl GipSAT {
         }
         let solver = unsafe { &*unblock.sat.solver };
         solver.vsids.activity.sort_by_activity(&mut latchs, false);
+        let lift_start = Instant::now();
+        if let LiftMode::Ternary = mode {
+            let res = self.ternary.lift(&assumption, &latchs, &target);
+            self.observer
+                .ternary_lift(res.as_ref().map(|r| r.len()), lift_start.elapsed());
+            if let Some(res) = res {
+                self.statistic.avg_ternary_lift_time += lift_start.elapsed();
+                self.statistic.lift_time += lift_start.elapsed();
+                self.statistic.avg_ternary_lift_len += res.len() as f64;
+                return res;
+            }
+            self.statistic.num_ternary_lift_fail += 1;
+        }
+        let start = Instant::now();
+        self.observer.query_started(Query::Lift);
         assumption.extend_from_slice(&latchs);
-        let res: Cube = match self.lift.solve_with_constrain(&assumption, cls, false) {
+        let res: Cube = match self.lift.solve_with_constrain(&assumption, !target, false) {
             SatResult::Sat(_) => panic!(),
             SatResult::Unsat(conflict) => latchs.into_iter().filter(|l| conflict.has(*l)).collect(),
         };
+        self.statistic.avg_sat_lift_time += start.elapsed();
+        self.statistic.lift_time += lift_start.elapsed();
+        self.statistic.avg_sat_lift_len += res.len() as f64;
+        self.exchange_learnt();
+        self.observer
+            .query_finished(Query::Lift, false, start.elapsed());
         res
     }
 
     /// perform the propagation.
     pub fn propagate(&mut self) -> bool {
+        self.recorded(Self::propagate_inner, |res| Call::Propagate(*res))
+    }
+
+    fn propagate_inner(&mut self) -> bool {
         for frame_idx in self.early..self.level() {
             self.frame[frame_idx].sort_by_key(|x| x.len());
             let frame = self.frame[frame_idx].clone();
             for lemma in frame {
-                if self.frame[frame_idx].iter().all(|l| l.lemma != lemma.lemma) {
+                // the lemma may have been removed and its id reused meanwhile
+                if self.index.frame(lemma.id) != Some(frame_idx)
+                    || *self.index.lemma(lemma.id) != lemma.lemma
+                {
                     continue;
                 }
                 if self.inductive(frame_idx + 1, &lemma, false) {
@@ -677,45 +1183,155 @@ impl GipSAT {
     }
 
     pub fn has_bad(&mut self) -> bool {
+        self.has_bad_property(0)
+    }
+
+    fn has_bad_cube(&mut self, bad: Cube) -> bool {
+        let call = bad.clone();
+        self.recorded(
+            |gipsat| gipsat.has_bad_cube_inner(bad),
+            |res| Call::HasBad(call, *res),
+        )
+    }
+
+    fn has_bad_cube_inner(&mut self, bad: Cube) -> bool {
         let start = Instant::now();
         self.statistic.num_sat += 1;
-        let res = match self
-            .solvers
-            .last_mut()
-            .unwrap()
-            .solve_with_domain(&self.ts.bad, false)
-        {
+        self.observer.query_started(Query::Bad(self.level()));
+        let solver_idx = self.solver_of(self.level());
+        self.recycle_solver(solver_idx);
+        let mut assump = self.frame_act(self.level());
+        assump.extend_from_slice(&bad);
+        let res = match self.solvers[solver_idx].solve_with_domain(&assump, false) {
             SatResult::Sat(sat) => {
                 self.last_ind = Some(BlockResult::No(BlockResultNo {
                     sat,
-                    assumption: self.ts.bad.clone(),
+                    assumption: bad,
                 }));
                 true
             }
             SatResult::Unsat(_) => false,
         };
         self.statistic.avg_sat_time += start.elapsed();
+        self.statistic.bad_time += start.elapsed();
+        self.exchange_learnt();
+        self.observer
+            .query_finished(Query::Bad(self.level()), res, start.elapsed());
         res
     }
 
     pub fn set_domain(&mut self, frame: usize, domain: impl Iterator<Item = Lit>) {
-        self.solvers[frame].set_domain(domain)
+        let domain = Cube::from_iter(domain);
+        let call = Call::SetDomain(frame, domain.clone());
+        self.recorded(
+            |gipsat| {
+                let solver_idx = gipsat.solver_of(frame);
+                gipsat.solvers[solver_idx].set_domain(domain.into_iter())
+            },
+            |_| call,
+        )
     }
 
     pub fn unset_domain(&mut self, frame: usize) {
-        self.solvers[frame].unset_domain()
+        self.recorded(
+            |gipsat| {
+                let solver_idx = gipsat.solver_of(frame);
+                gipsat.solvers[solver_idx].unset_domain()
+            },
+            |_| Call::UnsetDomain(frame),
+        )
     }
 
-    pub fn statistic(&self) {
-        for f in self.frame.iter() {
-            print!("{} ", f.len());
+    /// get the cdcl activity of the variable in the solver of the frame
+    #[inline]
+    pub fn frame_activity(&self, frame: usize, var: Var) -> f64 {
+        self.solvers[self.solver_of(frame)].vsids.activity[var]
+    }
+
+    /// get the cdcl activity of the variable summed over all frame solvers
+    pub fn aggregate_activity(&self, var: Var) -> f64 {
+        self.solvers.iter().map(|s| s.vsids.activity[var]).sum()
+    }
+
+    /// get the caller side activity of the variable, which is independent of the
+    /// cdcl activity of solvers
+    #[inline]
+    pub fn activity(&self, var: Var) -> f64 {
+        self.activity[var]
+    }
+
+    /// bump the caller side activity of the variables in the cube
+    pub fn bump_activity(&mut self, cube: &[Lit]) {
+        for l in cube.iter() {
+            self.activity.bump(l.var());
         }
-        println!();
-        let mut statistic = SolverStatistic::default();
+    }
+
+    /// decay the caller side activity
+    #[inline]
+    pub fn decay_activity(&mut self) {
+        self.activity.decay()
+    }
+
+    /// sort the cube by the caller side activity
+    #[inline]
+    pub fn sort_by_activity(&self, cube: &mut Cube, ascending: bool) {
+        self.activity.sort_by_activity(cube, ascending)
+    }
+
+    /// get the statistics of GipSAT
+    pub fn statistics(&self) -> Statistics {
+        let mut solver = self.lift.statistic;
+        let mut clause_memory = self.lift.cdb.memory();
         for s in self.solvers.iter() {
-            statistic = statistic + s.statistic;
+            solver = solver + s.statistic;
+            clause_memory += s.cdb.memory();
+        }
+        Statistics {
+            frames: self
+                .frame
+                .iter()
+                .enumerate()
+                .map(|(i, f)| FrameStatistic {
+                    num_lemma: f.len(),
+                    num_solve: if self.shared {
+                        0
+                    } else {
+                        self.solvers[i].statistic.num_solve
+                    },
+                })
+                .collect(),
+            num_solver: self.solvers.len() + 1,
+            clause_memory,
+            num_sat: self.statistic.num_sat,
+            num_solve: solver.num_solve,
+            num_conflict: solver.num_conflict,
+            num_decision: solver.num_decision,
+            num_propagation: solver.num_propagation,
+            num_restart: solver.num_restart,
+            num_learnt: solver.num_learnt,
+            num_deleted: solver.num_deleted,
+            num_gc: solver.num_gc,
+            num_recycle: solver.num_recycle,
+            num_import: solver.num_import,
+            num_ternary_lift_fail: self.statistic.num_ternary_lift_fail,
+            cone_cache_size: solver.cone_cache_size,
+            avg_decide_var: solver.avg_decide_var.value(),
+            avg_sat_lift_len: self.statistic.avg_sat_lift_len.value(),
+            avg_ternary_lift_len: self.statistic.avg_ternary_lift_len.value(),
+            inductive_time: self.statistic.inductive_time,
+            bad_time: self.statistic.bad_time,
+            lift_time: self.statistic.lift_time,
+            cone_precompute_time: solver.cone_precompute_time,
+            domain_time: solver.domain_time,
+            avg_sat_time: self.statistic.avg_sat_time.value(),
+            avg_sat_lift_time: self.statistic.avg_sat_lift_time.value(),
+            avg_ternary_lift_time: self.statistic.avg_ternary_lift_time.value(),
         }
-        println!("{:#?}", statistic);
-        println!("{:#?}", self.statistic);
+    }
+
+    /// print the statistics of GipSAT
+    pub fn statistic(&self) {
+        print!("{}", self.statistics());
     }
 }
@@ -0,0 +1,302 @@
+use crate::{
+    ic3::{IC3Result, Step, IC3},
+    ternary::TernarySim,
+    GeneralizeConfig,
+};
+use logic_form::{Clause, Cube, Lit, Var};
+use std::{
+    fmt::{self, Display},
+    rc::Rc,
+};
+use transys::Transys;
+
+fn new_gate(ts: &mut Transys, dep: Vec<Var>, clauses: impl Fn(Lit) -> Vec<Clause>) -> Lit {
+    let var = ts.new_var();
+    ts.trans.extend(clauses(var.lit()));
+    ts.dependence.reserve(var);
+    ts.dependence[var] = dep;
+    var.lit()
+}
+
+fn new_and(ts: &mut Transys, a: Lit, b: Lit) -> Lit {
+    new_gate(ts, vec![a.var(), b.var()], |n| {
+        vec![
+            Clause::from([!n, a]),
+            Clause::from([!n, b]),
+            Clause::from([n, !a, !b]),
+        ]
+    })
+}
+
+fn new_or(ts: &mut Transys, a: Lit, b: Lit) -> Lit {
+    !new_and(ts, !a, !b)
+}
+
+fn new_eq(ts: &mut Transys, a: Lit, b: Lit) -> Lit {
+    new_gate(ts, vec![a.var(), b.var()], |n| {
+        vec![
+            Clause::from([!n, !a, b]),
+            Clause::from([!n, a, !b]),
+            Clause::from([n, a, b]),
+            Clause::from([n, !a, !b]),
+        ]
+    })
+}
+
+fn new_ite(ts: &mut Transys, c: Lit, t: Lit, e: Lit) -> Lit {
+    new_gate(ts, vec![c.var(), t.var(), e.var()], |n| {
+        vec![
+            Clause::from([!c, !t, n]),
+            Clause::from([!c, t, !n]),
+            Clause::from([c, !e, n]),
+            Clause::from([c, e, !n]),
+        ]
+    })
+}
+
+pub(crate) fn new_latch(
+    ts: &mut Transys,
+    next: impl FnOnce(&mut Transys, Lit) -> Lit,
+    init: Option<bool>,
+) -> Lit {
+    let state = ts.new_var();
+    ts.dependence.reserve(state);
+    let value = next(ts, state.lit());
+    let next = ts.new_var();
+    ts.dependence.reserve(next);
+    let trans = vec![
+        Clause::from([!next.lit(), value]),
+        Clause::from([next.lit(), !value]),
+    ];
+    ts.add_latch(state, next.lit(), init, trans, vec![value.var()]);
+    ts.max_latch = ts.max_latch.max(state);
+    state.lit()
+}
+
+/// a lasso shaped counterexample, the state of the last step equals the state of
+/// the step at loop_start
+#[derive(Clone, Debug, Default)]
+pub struct Lasso {
+    pub trace: Vec<Step>,
+    pub loop_start: usize,
+}
+
+pub enum LivenessResult {
+    Proved,
+    Lasso(Lasso),
+}
+
+/// the counterexample of the transformed system does not form a lasso
+#[derive(Debug)]
+pub enum LassoError {
+    /// no state is saved along the counterexample
+    NoSave,
+    /// the last state differs from the state saved at the step
+    LoopMismatch(usize),
+}
+
+impl Display for LassoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LassoError::NoSave => write!(f, "no state is saved in the counterexample"),
+            LassoError::LoopMismatch(step) => {
+                write!(
+                    f,
+                    "the last state differs from the state saved at step {step}"
+                )
+            }
+        }
+    }
+}
+
+/// liveness checking by liveness-to-safety transformation, checks whether all
+/// justice literals hold infinitely often on every fair path
+pub struct Liveness {
+    ts: Rc<Transys>,
+    num_var: usize,
+    save: Lit,
+}
+
+impl Liveness {
+    pub fn new(ts: &Transys, justice: &[Lit]) -> Self {
+        let num_var = ts.num_var;
+        let latchs = ts.latchs.clone();
+        let mut ts = ts.clone();
+        let save = ts.new_var();
+        ts.dependence.reserve(save);
+        ts.inputs.push(save);
+        let save = save.lit();
+        let mut save_now = save;
+        let mut saved_next = save;
+        let saved = new_latch(
+            &mut ts,
+            |ts, saved| {
+                save_now = new_and(ts, save, !saved);
+                saved_next = new_or(ts, saved, save);
+                saved_next
+            },
+            Some(false),
+        );
+        let mut bad = Cube::from([saved]);
+        for l in latchs {
+            let shadow = new_latch(&mut ts, |ts, s| new_ite(ts, save_now, l.lit(), s), None);
+            bad.push(new_eq(&mut ts, shadow, l.lit()));
+        }
+        for j in justice.iter() {
+            let seen = new_latch(
+                &mut ts,
+                |ts, seen| {
+                    let j = new_and(ts, *j, saved_next);
+                    new_or(ts, seen, j)
+                },
+                Some(false),
+            );
+            bad.push(seen);
+        }
+        ts.bad = bad;
+        Self {
+            ts: Rc::new(ts),
+            num_var,
+            save,
+        }
+    }
+
+    /// get the transition system after liveness-to-safety transformation
+    #[inline]
+    pub fn ts(&self) -> &Rc<Transys> {
+        &self.ts
+    }
+
+    fn map_back(&self, cube: &Cube) -> Cube {
+        cube.iter()
+            .filter(|l| Into::<usize>::into(l.var()) < self.num_var)
+            .copied()
+            .collect()
+    }
+
+    /// simulate the lifted counterexample from the initial state, the inputs and
+    /// latchs left out by lifting do not matter and are set to their initial value
+    /// or false, returns the complete steps
+    fn simulate(&self, trace: &[Step]) -> Vec<Step> {
+        let ts = &self.ts;
+        let mut sim = TernarySim::new(ts);
+        let mut model: Cube = (0..ts.num_var)
+            .map(|v| Lit::new(Var::new(v), false))
+            .collect();
+        let set = |model: &mut Cube, lit: Lit| model[Into::<usize>::into(lit.var())] = lit;
+        for l in ts.latchs.iter() {
+            let init = ts.init_map.get(l).copied().unwrap_or(false);
+            set(&mut model, Lit::new(*l, init));
+        }
+        for l in trace[0].latchs.iter() {
+            set(&mut model, *l);
+        }
+        let mut steps = Vec::new();
+        for step in trace.iter() {
+            for i in ts.inputs.iter() {
+                set(&mut model, Lit::new(*i, false));
+            }
+            for l in step.inputs.iter() {
+                set(&mut model, *l);
+            }
+            sim.simulate_model(&mut model);
+            let value = |vars: &[Var]| -> Cube {
+                vars.iter()
+                    .map(|v| model[Into::<usize>::into(*v)])
+                    .collect()
+            };
+            steps.push(Step {
+                latchs: value(&ts.latchs),
+                inputs: value(&ts.inputs),
+            });
+            let next: Vec<Lit> = ts
+                .latchs
+                .iter()
+                .map(|l| {
+                    let next = ts.lit_next(l.lit());
+                    let value = model[Into::<usize>::into(next.var())];
+                    Lit::new(*l, value.polarity() == next.polarity())
+                })
+                .collect();
+            for l in next {
+                set(&mut model, l);
+            }
+        }
+        steps
+    }
+
+    /// check liveness, the lasso starts at the step saving the state which the
+    /// last state must equal, an error is returned if the counterexample does not
+    /// close the loop
+    pub fn check(&self, config: GeneralizeConfig) -> Result<LivenessResult, LassoError> {
+        let mut ic3 = IC3::new(self.ts.clone(), config);
+        match ic3.check() {
+            IC3Result::Safe => Ok(LivenessResult::Proved),
+            IC3Result::Unsafe(trace) => {
+                let trace = self.simulate(&trace);
+                let loop_start = trace
+                    .iter()
+                    .position(|s| s.inputs.contains(&self.save))
+                    .ok_or(LassoError::NoSave)?;
+                let last = &trace[trace.len() - 1];
+                if self.map_back(&trace[loop_start].latchs) != self.map_back(&last.latchs) {
+                    return Err(LassoError::LoopMismatch(loop_start));
+                }
+                let trace = trace
+                    .iter()
+                    .map(|s| Step {
+                        latchs: self.map_back(&s.latchs),
+                        inputs: self.map_back(&s.inputs),
+                    })
+                    .collect();
+                Ok(LivenessResult::Lasso(Lasso { trace, loop_start }))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Liveness, LivenessResult};
+    use crate::{
+        testing::{assert_path, load, simulate},
+        GeneralizeConfig,
+    };
+
+    /// a latch initialized to zero taking the value of an input
+    const LATCH: &str = "aag 2 1 1 1 0\n2\n4 2\n4\n";
+
+    /// a latch initialized to zero keeping its value
+    const STUCK: &str = "aag 1 0 1 1 0\n2 2\n2\n";
+
+    #[test]
+    fn lasso_maps_back_to_counterexample() {
+        let ts = load("lasso", LATCH);
+        let justice = ts.latchs[0].lit();
+        let liveness = Liveness::new(&ts, &[justice]);
+        let Ok(LivenessResult::Lasso(lasso)) = liveness.check(GeneralizeConfig::default()) else {
+            panic!("no lasso found");
+        };
+        let trace = &lasso.trace;
+        assert!(lasso.loop_start < trace.len() - 1);
+        assert_path(&ts, trace);
+        assert_eq!(
+            trace[lasso.loop_start].latchs,
+            trace[trace.len() - 1].latchs
+        );
+        assert!(trace[lasso.loop_start..].iter().any(|step| {
+            let model = simulate(&ts, step);
+            model[Into::<usize>::into(justice.var())] == justice
+        }));
+    }
+
+    #[test]
+    fn unreachable_justice_is_proved() {
+        let ts = load("stuck", STUCK);
+        let liveness = Liveness::new(&ts, &[ts.latchs[0].lit()]);
+        assert!(matches!(
+            liveness.check(GeneralizeConfig::default()),
+            Ok(LivenessResult::Proved)
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::Solver;
+use logic_form::{Clause, Cube, Lit};
+use satif::SatResult;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoreMinimize {
+    /// try to drop the literals of the core one by one
+    Deletion,
+    /// divide and conquer minimization of QuickXplain
+    QuickXplain,
+}
+
+impl Solver {
+    fn core_unsat(
+        &mut self,
+        assump: &[Lit],
+        constrain: &Option<Clause>,
+        budget: &mut usize,
+    ) -> bool {
+        if *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+        let res = match constrain {
+            Some(constrain) => self.solve_with_constrain(assump, constrain.clone(), true),
+            None => self.solve_with_domain(assump, true),
+        };
+        matches!(res, SatResult::Unsat(_))
+    }
+
+    fn deletion(&mut self, mut core: Cube, constrain: &Option<Clause>, budget: &mut usize) -> Cube {
+        let mut i = 0;
+        while i < core.len() {
+            let mut removed = core.clone();
+            removed.remove(i);
+            if self.core_unsat(&removed, constrain, budget) {
+                core = removed
+                    .into_iter()
+                    .filter(|l| self.unsat_core.has(*l))
+                    .collect();
+            } else {
+                i += 1;
+            }
+        }
+        core
+    }
+
+    fn quickxplain(
+        &mut self,
+        background: &mut Cube,
+        check: bool,
+        candidate: &[Lit],
+        constrain: &Option<Clause>,
+        budget: &mut usize,
+    ) -> Cube {
+        if check && self.core_unsat(background, constrain, budget) {
+            return Cube::new();
+        }
+        if candidate.len() <= 1 {
+            return Cube::from(candidate);
+        }
+        let (c1, c2) = candidate.split_at(candidate.len() / 2);
+        let len = background.len();
+        background.extend_from_slice(c1);
+        let d2 = self.quickxplain(background, true, c2, constrain, budget);
+        background.truncate(len);
+        background.extend_from_slice(&d2);
+        let d1 = self.quickxplain(background, !d2.is_empty(), c1, constrain, budget);
+        background.truncate(len);
+        let mut core = d1;
+        core.extend_from_slice(&d2);
+        core
+    }
+
+    /// minimize the unsat core of the assumption under the constrain, spending at
+    /// most budget solves, the unsat core of the solver is set to the result
+    pub fn minimize_core(
+        &mut self,
+        core: &[Lit],
+        constrain: Option<Clause>,
+        method: CoreMinimize,
+        mut budget: usize,
+    ) -> Cube {
+        let core = match method {
+            CoreMinimize::Deletion => self.deletion(Cube::from(core), &constrain, &mut budget),
+            CoreMinimize::QuickXplain => {
+                self.quickxplain(&mut Cube::new(), false, core, &constrain, &mut budget)
+            }
+        };
+        self.unsat_core.clear();
+        for l in core.iter() {
+            self.unsat_core.insert(*l);
+        }
+        core
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CoreMinimize;
+    use crate::{testing::load, GipSAT};
+    use logic_form::Cube;
+
+    /// latchs x and y initialized to zero swapping their values, latch z taking
+    /// an input and latch w taking z, w is bad
+    const SWAP: &str = "aag 5 1 4 1 0\n2\n4 6\n6 4\n8 2\n10 8\n10\n";
+
+    #[test]
+    fn minimized_core_stays_unsat() {
+        let ts = load("minimize", SWAP);
+        let cube: Cube = ts.latchs.iter().map(|l| l.lit()).collect();
+        let mut gipsat = GipSAT::new(ts);
+        gipsat.extend();
+        gipsat.extend();
+        for method in [CoreMinimize::Deletion, CoreMinimize::QuickXplain] {
+            assert!(gipsat.inductive(1, &cube, false));
+            let core = gipsat.minimal_inductive_core(method, 100);
+            assert!(core.len() <= cube.len());
+            assert!(core.iter().all(|l| cube.contains(l)));
+            assert!(gipsat.inductive(1, &core, false));
+        }
+    }
+}
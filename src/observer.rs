@@ -0,0 +1,190 @@
+use crate::record::lit_to_dimacs;
+use logic_form::Cube;
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    time::Duration,
+    time::Instant,
+};
+
+/// a sat query issued by GipSAT
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Query {
+    /// relative inductive query to the frame
+    Inductive(usize),
+    /// intersection of the frame with the bad states
+    Bad(usize),
+    /// lifting of a predecessor
+    Lift,
+}
+
+/// observer of GipSAT events, every event defaults to a no-op
+pub trait Observer {
+    fn frame_extended(&self, _level: usize) {}
+
+    fn lemma_added(&self, _frame: usize, _lemma: &Cube) {}
+
+    /// a lemma of the frame is removed as it is subsumed by a new lemma
+    fn lemma_subsumed(&self, _frame: usize, _lemma: &Cube) {}
+
+    fn lemma_pushed(&self, _from: usize, _to: usize, _lemma: &Cube) {}
+
+    fn query_started(&self, _query: Query) {}
+
+    /// sat is whether the query is satisfiable
+    fn query_finished(&self, _query: Query, _sat: bool, _time: Duration) {}
+
+    /// restart of the solver of the frame, none for the lift solver
+    fn restart(&self, _solver: Option<usize>) {}
+
+    /// garbage collection of the solver of the frame, none for the lift solver
+    fn garbage_collect(&self, _solver: Option<usize>) {}
+
+    /// ternary lifting of a predecessor, lifted is the length of the lifted cube,
+    /// none if the lifting failed and sat lifting follows
+    fn ternary_lift(&self, _lifted: Option<usize>, _time: Duration) {}
+
+    /// the solver of the frame is rebuilt by the recycle policy
+    fn solver_recycled(&self, _solver: usize) {}
+
+    /// shared learnt clauses imported by the solver of the frame, none for the
+    /// lift solver
+    fn clauses_imported(&self, _solver: Option<usize>, _num: usize) {}
+}
+
+pub struct NoObserver;
+
+impl Observer for NoObserver {}
+
+/// observer writing every event as a line of json, lemmas are given as dimacs
+/// literals and times in seconds. the first write error stops the logging and is
+/// kept until taken
+pub struct JsonLogger {
+    writer: RefCell<Box<dyn Write>>,
+    error: RefCell<Option<io::Error>>,
+    start: Instant,
+}
+
+impl JsonLogger {
+    pub fn new(writer: impl Write + 'static) -> Self {
+        Self {
+            writer: RefCell::new(Box::new(writer)),
+            error: RefCell::new(None),
+            start: Instant::now(),
+        }
+    }
+
+    /// take the write error which stopped the logging
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error.borrow_mut().take()
+    }
+
+    fn log(&self, event: &str, fields: &str) {
+        let mut error = self.error.borrow_mut();
+        if error.is_some() {
+            return;
+        }
+        let time = self.start.elapsed().as_secs_f64();
+        if let Err(e) = writeln!(
+            self.writer.borrow_mut(),
+            "{{\"event\":\"{event}\",\"time\":{time}{fields}}}"
+        ) {
+            *error = Some(e);
+        }
+    }
+}
+
+fn lemma_json(lemma: &Cube) -> String {
+    let lits: Vec<String> = lemma
+        .iter()
+        .map(|l| lit_to_dimacs(*l).to_string())
+        .collect();
+    format!("[{}]", lits.join(","))
+}
+
+fn query_json(query: Query) -> String {
+    match query {
+        Query::Inductive(frame) => format!(",\"query\":\"inductive\",\"frame\":{frame}"),
+        Query::Bad(frame) => format!(",\"query\":\"bad\",\"frame\":{frame}"),
+        Query::Lift => ",\"query\":\"lift\"".to_string(),
+    }
+}
+
+fn solver_json(solver: Option<usize>) -> String {
+    match solver {
+        Some(solver) => format!(",\"solver\":{solver}"),
+        None => ",\"solver\":null".to_string(),
+    }
+}
+
+impl Observer for JsonLogger {
+    fn frame_extended(&self, level: usize) {
+        self.log("frame_extended", &format!(",\"level\":{level}"));
+    }
+
+    fn lemma_added(&self, frame: usize, lemma: &Cube) {
+        let lemma = lemma_json(lemma);
+        self.log(
+            "lemma_added",
+            &format!(",\"frame\":{frame},\"lemma\":{lemma}"),
+        );
+    }
+
+    fn lemma_subsumed(&self, frame: usize, lemma: &Cube) {
+        let lemma = lemma_json(lemma);
+        self.log(
+            "lemma_subsumed",
+            &format!(",\"frame\":{frame},\"lemma\":{lemma}"),
+        );
+    }
+
+    fn lemma_pushed(&self, from: usize, to: usize, lemma: &Cube) {
+        let lemma = lemma_json(lemma);
+        self.log(
+            "lemma_pushed",
+            &format!(",\"from\":{from},\"to\":{to},\"lemma\":{lemma}"),
+        );
+    }
+
+    fn query_started(&self, query: Query) {
+        self.log("query_started", &query_json(query));
+    }
+
+    fn query_finished(&self, query: Query, sat: bool, time: Duration) {
+        let query = query_json(query);
+        let time = time.as_secs_f64();
+        self.log(
+            "query_finished",
+            &format!("{query},\"sat\":{sat},\"duration\":{time}"),
+        );
+    }
+
+    fn restart(&self, solver: Option<usize>) {
+        self.log("restart", &solver_json(solver));
+    }
+
+    fn garbage_collect(&self, solver: Option<usize>) {
+        self.log("garbage_collect", &solver_json(solver));
+    }
+
+    fn ternary_lift(&self, lifted: Option<usize>, time: Duration) {
+        let time = time.as_secs_f64();
+        let lifted = match lifted {
+            Some(len) => len.to_string(),
+            None => "null".to_string(),
+        };
+        self.log(
+            "ternary_lift",
+            &format!(",\"len\":{lifted},\"duration\":{time}"),
+        );
+    }
+
+    fn solver_recycled(&self, solver: usize) {
+        self.log("solver_recycled", &format!(",\"solver\":{solver}"));
+    }
+
+    fn clauses_imported(&self, solver: Option<usize>, num: usize) {
+        let solver = solver_json(solver);
+        self.log("clauses_imported", &format!("{solver},\"num\":{num}"));
+    }
+}
@@ -56,6 +56,7 @@ impl Solver {
         while self.propagated < self.trail.len() {
             let p = self.trail[self.propagated];
             self.propagated += 1;
+            self.statistic.num_propagation += 1;
             let mut w = 0;
             'next_cls: while w < self.watchers.wtrs[p].len() {
                 let watchers = &mut self.watchers.wtrs[p];
@@ -0,0 +1,99 @@
+use crate::{coi::coi_covers, Call, GipSAT};
+use logic_form::{Clause, Cube};
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PropertyStatus {
+    #[default]
+    Unknown,
+    Proved,
+    Falsified,
+}
+
+pub struct Property {
+    bad: Cube,
+    status: PropertyStatus,
+}
+
+impl Property {
+    #[inline]
+    pub fn new(bad: Cube) -> Self {
+        Self {
+            bad,
+            status: PropertyStatus::Unknown,
+        }
+    }
+}
+
+impl GipSAT {
+    /// add a property given by its bad cube, returns the index of the property.
+    /// the property of the transition system has index 0. with coi reduction the
+    /// property is rejected with none if its cone is not in the reduced system
+    pub fn add_property(&mut self, bad: Cube) -> Option<usize> {
+        let call = bad.clone();
+        self.recorded(
+            |gipsat| gipsat.add_property_inner(bad),
+            |res| Call::AddProperty(call, *res),
+        )
+    }
+
+    fn add_property_inner(&mut self, bad: Cube) -> Option<usize> {
+        if !Rc::ptr_eq(&self.ts, &self.solver_ts) && !coi_covers(&self.ts, &self.solver_ts, &bad) {
+            return None;
+        }
+        self.properties.push(Property::new(bad));
+        Some(self.properties.len() - 1)
+    }
+
+    #[inline]
+    pub fn num_property(&self) -> usize {
+        self.properties.len()
+    }
+
+    #[inline]
+    pub fn property_status(&self, property: usize) -> PropertyStatus {
+        self.properties[property].status
+    }
+
+    /// query whether the highest frame intersects the bad states of the property
+    pub fn has_bad_property(&mut self, property: usize) -> bool {
+        let bad = self.properties[property].bad.clone();
+        self.has_bad_cube(bad)
+    }
+
+    /// query whether the highest frame intersects the bad states of any unsolved
+    /// property, returns the index of the property
+    pub fn has_bad_any(&mut self) -> Option<usize> {
+        (0..self.properties.len()).find(|p| {
+            self.properties[*p].status == PropertyStatus::Unknown && self.has_bad_property(*p)
+        })
+    }
+
+    pub fn set_falsified(&mut self, property: usize) {
+        self.recorded(
+            |gipsat| gipsat.properties[property].status = PropertyStatus::Falsified,
+            |_| Call::SetFalsified(property),
+        )
+    }
+
+    /// mark the property as proved, it is added as a constraint of all frames to
+    /// help solving the remaining properties
+    pub fn set_proved(&mut self, property: usize) {
+        self.recorded(
+            |gipsat| gipsat.set_proved_inner(property),
+            |_| Call::SetProved(property),
+        )
+    }
+
+    fn set_proved_inner(&mut self, property: usize) {
+        if self.properties[property].status == PropertyStatus::Proved {
+            return;
+        }
+        self.properties[property].status = PropertyStatus::Proved;
+        let constrain: Clause = !&self.properties[property].bad;
+        for s in self.solvers.iter_mut() {
+            s.add_constrain(&constrain);
+        }
+        self.invariants.push(constrain);
+    }
+}
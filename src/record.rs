@@ -0,0 +1,479 @@
+use crate::{CoreMinimize, GipSAT, LiftMode, RecyclePolicy, ShareConfig};
+use logic_form::{Clause, Cube, Lit, Var};
+use std::{
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufRead, LineWriter, Write},
+    path::Path,
+    rc::Rc,
+};
+use transys::Transys;
+
+/// a public call on GipSAT together with its result, literals are written in
+/// dimacs style
+#[derive(Clone, Debug, PartialEq)]
+pub enum Call {
+    /// construction of GipSAT, whether the coi reduction and the single solver
+    /// mode are enabled
+    New(bool, bool),
+    /// derivation of the transition system, the rst argument of Transys::from_aig
+    /// if known, the number of variables, inputs, latchs and transition clauses
+    Transys(Option<bool>, usize, usize, usize, usize),
+    Extend,
+    AddLemma(usize, Cube),
+    Inductive(usize, Cube, bool, bool),
+    InductiveCore(Option<(CoreMinimize, usize)>, Cube),
+    Predecessor(LiftMode, Cube),
+    Propagate(bool),
+    HasBad(Cube, bool),
+    SetDomain(usize, Cube),
+    UnsetDomain(usize),
+    RemoveLemma(usize, Cube, bool),
+    ResetToLevel(usize),
+    AddProperty(Cube, Option<usize>),
+    SetProved(usize),
+    SetFalsified(usize),
+    AddInfLemma(Cube),
+    AddConstraint(Clause),
+    PromoteInvariants(usize),
+    SetRecyclePolicy(Option<RecyclePolicy>),
+    SetShareConfig(Option<ShareConfig>),
+    SetPrecomputeCone(bool),
+}
+
+pub(crate) fn lit_to_dimacs(lit: Lit) -> i64 {
+    let var = Into::<usize>::into(lit.var()) as i64 + 1;
+    if lit.polarity() {
+        var
+    } else {
+        -var
+    }
+}
+
+pub(crate) fn lit_from_dimacs(lit: i64) -> Lit {
+    Lit::new(Var::new(lit.unsigned_abs() as usize - 1), lit > 0)
+}
+
+struct Cubes<'a>(&'a [Lit]);
+
+impl Display for Cubes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for l in self.0.iter() {
+            write!(f, " {}", lit_to_dimacs(*l))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Call::New(coi, shared) => write!(f, "new {} {}", *coi as u8, *shared as u8),
+            Call::Transys(rst, num_var, num_input, num_latch, num_trans) => {
+                let rst = match rst {
+                    None => "none".to_string(),
+                    Some(rst) => (*rst as u8).to_string(),
+                };
+                write!(
+                    f,
+                    "transys {rst} {num_var} {num_input} {num_latch} {num_trans}"
+                )
+            }
+            Call::Extend => write!(f, "extend"),
+            Call::AddLemma(frame, lemma) => write!(f, "add_lemma {frame}{}", Cubes(lemma)),
+            Call::Inductive(frame, cube, strengthen, res) => write!(
+                f,
+                "inductive {frame} {} {}{}",
+                *strengthen as u8,
+                *res as u8,
+                Cubes(cube)
+            ),
+            Call::InductiveCore(minimize, core) => {
+                let minimize = match minimize {
+                    None => "none 0".to_string(),
+                    Some((CoreMinimize::Deletion, budget)) => format!("deletion {budget}"),
+                    Some((CoreMinimize::QuickXplain, budget)) => format!("quickxplain {budget}"),
+                };
+                write!(f, "inductive_core {minimize}{}", Cubes(core))
+            }
+            Call::Predecessor(mode, cube) => {
+                let mode = match mode {
+                    LiftMode::Sat => "sat",
+                    LiftMode::Ternary => "ternary",
+                };
+                write!(f, "predecessor {mode}{}", Cubes(cube))
+            }
+            Call::Propagate(res) => write!(f, "propagate {}", *res as u8),
+            Call::HasBad(bad, res) => write!(f, "has_bad {}{}", *res as u8, Cubes(bad)),
+            Call::SetDomain(frame, domain) => write!(f, "set_domain {frame}{}", Cubes(domain)),
+            Call::UnsetDomain(frame) => write!(f, "unset_domain {frame}"),
+            Call::RemoveLemma(frame, lemma, res) => {
+                write!(f, "remove_lemma {frame} {}{}", *res as u8, Cubes(lemma))
+            }
+            Call::ResetToLevel(level) => write!(f, "reset_to_level {level}"),
+            Call::AddProperty(bad, Some(property)) => {
+                write!(f, "add_property {property}{}", Cubes(bad))
+            }
+            Call::AddProperty(bad, None) => write!(f, "add_property none{}", Cubes(bad)),
+            Call::SetProved(property) => write!(f, "set_proved {property}"),
+            Call::SetFalsified(property) => write!(f, "set_falsified {property}"),
+            Call::AddInfLemma(lemma) => write!(f, "add_inf_lemma{}", Cubes(lemma)),
+            Call::AddConstraint(clause) => write!(f, "add_constraint{}", Cubes(clause)),
+            Call::PromoteInvariants(num) => write!(f, "promote_invariants {num}"),
+            Call::SetRecyclePolicy(None) => write!(f, "set_recycle_policy none"),
+            Call::SetRecyclePolicy(Some(policy)) => write!(
+                f,
+                "set_recycle_policy {} {} {}",
+                policy.max_query, policy.max_learnt_ratio, policy.max_wasted_ratio
+            ),
+            Call::SetShareConfig(None) => write!(f, "set_share_config none"),
+            Call::SetShareConfig(Some(config)) => {
+                write!(f, "set_share_config {} {}", config.max_len, config.max_lbd)
+            }
+            Call::SetPrecomputeCone(enable) => {
+                write!(f, "set_precompute_cone {}", *enable as u8)
+            }
+        }
+    }
+}
+
+fn parse_cube<'a>(token: impl Iterator<Item = &'a str>) -> Option<Cube> {
+    token
+        .map(|l| {
+            l.parse::<i64>()
+                .ok()
+                .filter(|l| *l != 0)
+                .map(lit_from_dimacs)
+        })
+        .collect()
+}
+
+impl Call {
+    /// parse a call from a line of the record
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut token = line.split_whitespace().peekable();
+        let name = token.next()?;
+        if token.peek() == Some(&"none") {
+            token.next();
+            let mut number = || -> Option<usize> { token.next()?.parse().ok() };
+            if name == "transys" {
+                return Some(Call::Transys(
+                    None,
+                    number()?,
+                    number()?,
+                    number()?,
+                    number()?,
+                ));
+            }
+            return match name {
+                "set_recycle_policy" => Some(Call::SetRecyclePolicy(None)),
+                "set_share_config" => Some(Call::SetShareConfig(None)),
+                "add_property" => Some(Call::AddProperty(parse_cube(token)?, None)),
+                _ => None,
+            };
+        }
+        let mut number = || -> Option<usize> { token.next()?.parse().ok() };
+        Some(match name {
+            "new" => Call::New(number()? != 0, number()? != 0),
+            "transys" => Call::Transys(
+                Some(number()? != 0),
+                number()?,
+                number()?,
+                number()?,
+                number()?,
+            ),
+            "extend" => Call::Extend,
+            "add_lemma" => Call::AddLemma(number()?, parse_cube(token)?),
+            "inductive" => {
+                let frame = number()?;
+                let strengthen = number()? != 0;
+                let res = number()? != 0;
+                Call::Inductive(frame, parse_cube(token)?, strengthen, res)
+            }
+            "inductive_core" => {
+                let method = token.next()?;
+                let budget = token.next()?.parse().ok()?;
+                let minimize = match method {
+                    "none" => None,
+                    "deletion" => Some((CoreMinimize::Deletion, budget)),
+                    "quickxplain" => Some((CoreMinimize::QuickXplain, budget)),
+                    _ => return None,
+                };
+                Call::InductiveCore(minimize, parse_cube(token)?)
+            }
+            "predecessor" => {
+                let mode = match token.next()? {
+                    "sat" => LiftMode::Sat,
+                    "ternary" => LiftMode::Ternary,
+                    _ => return None,
+                };
+                Call::Predecessor(mode, parse_cube(token)?)
+            }
+            "propagate" => Call::Propagate(number()? != 0),
+            "has_bad" => {
+                let res = number()? != 0;
+                Call::HasBad(parse_cube(token)?, res)
+            }
+            "set_domain" => Call::SetDomain(number()?, parse_cube(token)?),
+            "unset_domain" => Call::UnsetDomain(number()?),
+            "remove_lemma" => {
+                let frame = number()?;
+                let res = number()? != 0;
+                Call::RemoveLemma(frame, parse_cube(token)?, res)
+            }
+            "reset_to_level" => Call::ResetToLevel(number()?),
+            "add_property" => {
+                let property = number()?;
+                Call::AddProperty(parse_cube(token)?, Some(property))
+            }
+            "set_proved" => Call::SetProved(number()?),
+            "set_falsified" => Call::SetFalsified(number()?),
+            "add_inf_lemma" => Call::AddInfLemma(parse_cube(token)?),
+            "add_constraint" => Call::AddConstraint(Clause::from_iter(parse_cube(token)?)),
+            "promote_invariants" => Call::PromoteInvariants(number()?),
+            "set_recycle_policy" => Call::SetRecyclePolicy(Some(RecyclePolicy {
+                max_query: number()?,
+                max_learnt_ratio: token.next()?.parse().ok()?,
+                max_wasted_ratio: token.next()?.parse().ok()?,
+            })),
+            "set_share_config" => Call::SetShareConfig(Some(ShareConfig {
+                max_len: number()?,
+                max_lbd: number()?,
+            })),
+            "set_precompute_cone" => Call::SetPrecomputeCone(number()? != 0),
+            _ => return None,
+        })
+    }
+
+    /// execute the call on GipSAT, returns the call with the actual result
+    fn execute(&self, gipsat: &mut GipSAT) -> Call {
+        match self {
+            Call::New(..) | Call::Transys(..) => self.clone(),
+            Call::Extend => {
+                gipsat.extend();
+                Call::Extend
+            }
+            Call::AddLemma(frame, lemma) => {
+                gipsat.add_lemma(*frame, lemma.clone());
+                self.clone()
+            }
+            Call::Inductive(frame, cube, strengthen, _) => {
+                let res = gipsat.inductive(*frame, cube, *strengthen);
+                Call::Inductive(*frame, cube.clone(), *strengthen, res)
+            }
+            Call::InductiveCore(minimize, _) => {
+                Call::InductiveCore(*minimize, gipsat.inductive_core_with(*minimize))
+            }
+            Call::Predecessor(mode, _) => {
+                Call::Predecessor(*mode, gipsat.get_predecessor_with(*mode))
+            }
+            Call::Propagate(_) => Call::Propagate(gipsat.propagate()),
+            Call::HasBad(bad, _) => Call::HasBad(bad.clone(), gipsat.has_bad_cube(bad.clone())),
+            Call::SetDomain(frame, domain) => {
+                gipsat.set_domain(*frame, domain.iter().copied());
+                self.clone()
+            }
+            Call::RemoveLemma(frame, lemma, _) => {
+                let res = gipsat.remove_lemma(*frame, lemma);
+                Call::RemoveLemma(*frame, lemma.clone(), res)
+            }
+            Call::ResetToLevel(level) => {
+                gipsat.reset_to_level(*level);
+                self.clone()
+            }
+            Call::UnsetDomain(frame) => {
+                gipsat.unset_domain(*frame);
+                self.clone()
+            }
+            Call::AddProperty(bad, _) => {
+                Call::AddProperty(bad.clone(), gipsat.add_property(bad.clone()))
+            }
+            Call::SetProved(property) => {
+                gipsat.set_proved(*property);
+                self.clone()
+            }
+            Call::SetFalsified(property) => {
+                gipsat.set_falsified(*property);
+                self.clone()
+            }
+            Call::AddInfLemma(lemma) => {
+                gipsat.add_inf_lemma(lemma.clone());
+                self.clone()
+            }
+            Call::AddConstraint(clause) => {
+                gipsat.add_constraint(clause.clone());
+                self.clone()
+            }
+            Call::PromoteInvariants(_) => Call::PromoteInvariants(gipsat.promote_invariants()),
+            Call::SetRecyclePolicy(policy) => {
+                gipsat.set_recycle_policy(*policy);
+                self.clone()
+            }
+            Call::SetShareConfig(config) => {
+                gipsat.set_share_config(*config);
+                self.clone()
+            }
+            Call::SetPrecomputeCone(enable) => {
+                gipsat.set_precompute_cone(*enable);
+                self.clone()
+            }
+        }
+    }
+}
+
+pub(crate) struct Recorder {
+    writer: LineWriter<File>,
+    /// the first io error, the calls after it are not recorded
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    fn record(&mut self, call: &Call) -> io::Result<()> {
+        writeln!(self.writer, "{call}")
+    }
+}
+
+impl GipSAT {
+    /// record every public call with its result into the file, the record can be
+    /// replayed with the replay binary. the record starts with the construction
+    /// mode, the derivation of the transition system and the current
+    /// configuration, so it should be started before any other call
+    pub fn record(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let writer = LineWriter::new(File::create(path)?);
+        let mut recorder = Recorder {
+            writer,
+            error: None,
+        };
+        recorder.record(&Call::New(
+            !Rc::ptr_eq(&self.ts, &self.solver_ts),
+            self.shared,
+        ))?;
+        recorder.record(&self.transys_call())?;
+        recorder.record(&Call::SetPrecomputeCone(self.precompute_cone))?;
+        recorder.record(&Call::SetRecyclePolicy(self.recycle))?;
+        recorder.record(&Call::SetShareConfig(self.share))?;
+        self.recorder = Some(recorder);
+        Ok(())
+    }
+
+    /// the derivation of the transition system written in the record header
+    fn transys_call(&self) -> Call {
+        Call::Transys(
+            self.aig_rst,
+            self.ts.num_var,
+            self.ts.inputs.len(),
+            self.ts.latchs.len(),
+            self.ts.trans.len(),
+        )
+    }
+
+    /// stop recording, returns the first io error of the recording
+    pub fn stop_record(&mut self) -> io::Result<()> {
+        let Some(mut recorder) = self.recorder.take() else {
+            return Ok(());
+        };
+        match recorder.error {
+            Some(error) => Err(error),
+            None => recorder.writer.flush(),
+        }
+    }
+
+    /// run the call and record it, calls nested in a recorded call are not recorded
+    pub(crate) fn recorded<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> T,
+        call: impl FnOnce(&T) -> Call,
+    ) -> T {
+        let Some(mut recorder) = self.recorder.take() else {
+            return f(self);
+        };
+        let res = f(self);
+        if recorder.error.is_none() {
+            if let Err(error) = recorder.record(&call(&res)) {
+                recorder.error = Some(error);
+            }
+        }
+        self.recorder = Some(recorder);
+        res
+    }
+}
+
+/// the first call whose replayed result differs from the record
+#[derive(Debug)]
+pub struct Divergence {
+    pub line: usize,
+    pub expected: Call,
+    pub actual: Call,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "divergence at line {}\n  expected: {}\n  actual:   {}",
+            self.line, self.expected, self.actual
+        )
+    }
+}
+
+/// the rst argument of Transys::from_aig the record was taken with, none if the
+/// record does not carry it
+pub fn record_aig_rst(record: impl BufRead) -> io::Result<Option<bool>> {
+    for line in record.lines() {
+        if let Some(Call::Transys(rst, ..)) = Call::parse(&line?) {
+            return Ok(rst);
+        }
+    }
+    Ok(None)
+}
+
+/// replay a record on a fresh GipSAT instance of the transition system, which is
+/// constructed as given by the first call of the record, returns the number of
+/// replayed calls or the first divergence. the transition system must match the
+/// one the record was taken on
+pub fn replay(ts: Rc<Transys>, record: impl BufRead) -> io::Result<Result<usize, Divergence>> {
+    let mut gipsat = None;
+    let mut num_call = 0;
+    for (i, line) in record.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = |msg: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{msg} at line {}: {line}", i + 1),
+            )
+        };
+        let Some(expected) = Call::parse(&line) else {
+            return Err(invalid("invalid call"));
+        };
+        if let Call::New(coi, shared) = expected {
+            if gipsat.is_some() {
+                return Err(invalid("construction after other calls"));
+            }
+            gipsat = Some(match (coi, shared) {
+                (_, true) => GipSAT::new_shared(ts.clone()),
+                (true, false) => GipSAT::new_with_coi(ts.clone()),
+                (false, false) => GipSAT::new(ts.clone()),
+            });
+        }
+        let gipsat = gipsat.get_or_insert_with(|| GipSAT::new(ts.clone()));
+        if let Call::Transys(rst, ..) = expected {
+            gipsat.aig_rst = rst;
+            if gipsat.transys_call() != expected {
+                return Err(invalid("transition system differs from the recorded one"));
+            }
+        }
+        let actual = expected.execute(gipsat);
+        if actual != expected {
+            return Ok(Err(Divergence {
+                line: i + 1,
+                expected,
+                actual,
+            }));
+        }
+        num_call += 1;
+    }
+    Ok(Ok(num_call))
+}
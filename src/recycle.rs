@@ -0,0 +1,82 @@
+use crate::{Call, GipSAT};
+
+/// policy to rebuild the solver of a frame from the transition relation and the
+/// lemmas, dropping its learnt clauses
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecyclePolicy {
+    /// rebuild after the solver answered this many queries
+    pub max_query: usize,
+    /// rebuild when the learnt clauses exceed this ratio of the transition clauses
+    pub max_learnt_ratio: f64,
+    /// rebuild when the wasted memory of the clause allocator exceeds this ratio
+    pub max_wasted_ratio: f64,
+}
+
+impl Default for RecyclePolicy {
+    fn default() -> Self {
+        Self {
+            max_query: 10000,
+            max_learnt_ratio: 1.0,
+            max_wasted_ratio: 0.5,
+        }
+    }
+}
+
+impl GipSAT {
+    /// set the policy of solver recycling, none disables recycling
+    #[inline]
+    pub fn set_recycle_policy(&mut self, policy: Option<RecyclePolicy>) {
+        self.recorded(
+            |gipsat| gipsat.recycle = policy,
+            |_| Call::SetRecyclePolicy(policy),
+        )
+    }
+
+    /// rebuild the solver if required by the recycle policy
+    pub(crate) fn recycle_solver(&mut self, id: usize) {
+        let Some(policy) = self.recycle else {
+            return;
+        };
+        let solver = &mut self.solvers[id];
+        if solver.temporary_domain {
+            return;
+        }
+        let num_query = solver.statistic.num_solve - solver.recycle_solve;
+        if num_query < policy.max_query
+            && solver.cdb.learnt_ratio() < policy.max_learnt_ratio
+            && solver.cdb.wasted_ratio() < policy.max_wasted_ratio
+        {
+            return;
+        }
+        self.rebuild_solver(id);
+        self.solvers[id].statistic.num_recycle += 1;
+        self.observer.solver_recycled(id);
+    }
+
+    /// rebuild the solver from the transition relation, the invariants and the
+    /// lemmas of the frames, dropping its learnt clauses and temporary domain. the
+    /// result of the last query refers to the old solver and is dropped
+    pub(crate) fn rebuild_solver(&mut self, id: usize) {
+        self.last_ind = None;
+        let mut solver = self.new_solver(id);
+        while solver.num_var() < self.solvers[id].num_var() {
+            solver.new_var();
+        }
+        for i in id..self.frame.len() {
+            for l in self.frame[i].iter_mut() {
+                let mut clause = !l.cube();
+                if self.shared {
+                    clause.push(!self.act[i]);
+                    l.set_cref(0, solver.add_lemma(&clause));
+                } else if i == 0 {
+                    solver.add_lemma(&clause);
+                } else if l.begin <= id {
+                    l.set_cref(id, solver.add_lemma(&clause));
+                }
+            }
+        }
+        solver.statistic = self.solvers[id].statistic;
+        solver.recycle_solve = solver.statistic.num_solve;
+        self.solvers[id] = solver;
+    }
+}
@@ -78,24 +78,37 @@ impl Solver {
                 Some(false) => return SatResult::Unsat(Unsat { solver: self }),
                 None => {
                     restarts += 1;
+                    self.statistic.num_restart += 1;
+                    self.observer.restart(self.id);
                 }
             }
         }
     }
 
     pub fn search(&mut self, assumption: &[Lit], noc: Option<f64>) -> Option<bool> {
+        if self.root_conflict {
+            self.unsat_core.clear();
+            return Some(false);
+        }
         let mut num_conflict = 0.0_f64;
         'ml: loop {
             let conflict = self.propagate();
             if conflict != CREF_NONE {
                 num_conflict += 1.0;
+                self.statistic.num_conflict += 1;
                 if self.highest_level() == 0 {
                     return Some(false);
                 }
                 let (learnt, btl) = self.analyze(conflict);
+                self.statistic.num_learnt += 1;
+                let trans_only = self.analyze.trans_only;
+                if trans_only {
+                    self.share_learnt(&learnt);
+                }
                 self.backtrack(btl, true);
                 if learnt.len() == 1 {
                     assert!(btl == 0);
+                    self.lemma_unit |= !trans_only;
                     self.assign(learnt[0], CREF_NONE);
                 } else {
                     let mut kind = ClauseKind::Learnt;
@@ -107,6 +120,7 @@ impl Solver {
                         }
                     }
                     let learnt_id = self.attach_clause(&learnt, kind);
+                    self.cdb.get(learnt_id).set_trans_only(trans_only);
                     self.cdb.bump(learnt_id);
                     let assign = self.cdb.get(learnt_id)[0];
                     self.assign(assign, learnt_id);
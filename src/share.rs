@@ -0,0 +1,116 @@
+use crate::{cdb::ClauseKind, Call, GipSAT, Solver, CREF_NONE};
+use logic_form::Clause;
+use std::mem::take;
+
+/// filter of the learnt clauses shared between solvers, only learnt clauses
+/// derived from transition clauses only are shared
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShareConfig {
+    pub max_len: usize,
+    pub max_lbd: usize,
+}
+
+impl Default for ShareConfig {
+    fn default() -> Self {
+        Self {
+            max_len: 8,
+            max_lbd: 4,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Share {
+    pub config: Option<ShareConfig>,
+    export: Vec<Clause>,
+    import: Vec<Clause>,
+}
+
+impl Solver {
+    pub(crate) fn share_learnt(&mut self, learnt: &Clause) {
+        let Some(config) = self.share.config else {
+            return;
+        };
+        if learnt.len() > config.max_len || self.calculate_lbd(learnt) > config.max_lbd {
+            return;
+        }
+        self.share.export.push(learnt.clone());
+    }
+
+    /// add the imported clauses at level zero, a clause conflicting with the
+    /// assignments of level zero is recorded as root conflict instead
+    pub(crate) fn import_shared(&mut self) {
+        let num_import = self.statistic.num_import;
+        for cls in take(&mut self.share.import) {
+            if self.root_conflict {
+                break;
+            }
+            let Some(cls) = self.reduce_clause(&cls) else {
+                continue;
+            };
+            self.statistic.num_import += 1;
+            match cls.len() {
+                0 => self.root_conflict = true,
+                1 => {
+                    self.assign(cls[0], CREF_NONE);
+                    self.root_conflict = self.propagate() != CREF_NONE;
+                }
+                _ => {
+                    let cref = self.attach_clause(&cls, ClauseKind::Learnt);
+                    self.cdb.get(cref).set_trans_only(true);
+                }
+            }
+        }
+        if self.statistic.num_import > num_import {
+            self.observer
+                .clauses_imported(self.id, self.statistic.num_import - num_import);
+        }
+    }
+}
+
+impl GipSAT {
+    /// share learnt clauses derived from transition clauses only between the frame
+    /// solvers and the lift solver, none disables sharing
+    pub fn set_share_config(&mut self, config: Option<ShareConfig>) {
+        self.recorded(
+            |gipsat| gipsat.set_share_config_inner(config),
+            |_| Call::SetShareConfig(config),
+        )
+    }
+
+    fn set_share_config_inner(&mut self, config: Option<ShareConfig>) {
+        self.share = config;
+        for s in self.solvers.iter_mut() {
+            s.share.config = config;
+        }
+        self.lift.share.config = config;
+    }
+
+    /// pass the exported learnt clauses of every solver to the others
+    pub(crate) fn exchange_learnt(&mut self) {
+        if self.share.is_none() {
+            return;
+        }
+        let num_solver = self.solvers.len() + 1;
+        for i in 0..num_solver {
+            let export = if i < self.solvers.len() {
+                take(&mut self.solvers[i].share.export)
+            } else {
+                take(&mut self.lift.share.export)
+            };
+            if export.is_empty() {
+                continue;
+            }
+            for (j, s) in self
+                .solvers
+                .iter_mut()
+                .chain(Some(&mut self.lift))
+                .enumerate()
+            {
+                if i != j {
+                    s.share.import.extend(export.iter().cloned());
+                }
+            }
+        }
+    }
+}
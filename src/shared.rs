@@ -0,0 +1,49 @@
+use crate::GipSAT;
+use logic_form::{Cube, Lit};
+use std::rc::Rc;
+use transys::Transys;
+
+impl GipSAT {
+    /// create a new GipSAT instance with a single solver holding the transition
+    /// relation once, the lemmas of each frame are guarded by an activation literal
+    /// of the frame which queries select by assumptions
+    pub fn new_shared(ts: Rc<Transys>) -> Self {
+        Self::new_inner(ts.clone(), ts, true)
+    }
+
+    /// whether GipSAT runs in single solver mode
+    #[inline]
+    pub fn is_shared(&self) -> bool {
+        self.shared
+    }
+
+    /// get the index of the solver answering the queries relative to the frame
+    #[inline]
+    pub(crate) fn solver_of(&self, frame: usize) -> usize {
+        if self.shared {
+            0
+        } else {
+            frame
+        }
+    }
+
+    /// activation literals selecting the lemmas of the frame in single solver mode,
+    /// lemmas of higher frames hold in lower frames except the initial frame
+    pub(crate) fn frame_act(&self, frame: usize) -> Cube {
+        if !self.shared {
+            Cube::new()
+        } else if frame == 0 {
+            Cube::from([self.act[0]])
+        } else {
+            self.act[frame..].iter().copied().collect()
+        }
+    }
+
+    /// disable the activation literals of the dropped frames, the guarded lemma
+    /// clauses and the learnt clauses derived from them are satisfied by that
+    pub(crate) fn drop_act(&mut self, level: usize) {
+        for act in self.act.drain(level + 1..).collect::<Vec<Lit>>() {
+            self.solvers[0].add_lemma(&[!act]);
+        }
+    }
+}
@@ -1,10 +1,87 @@
-use giputils::statistic::{Average, AverageDuration};
-use std::ops::Add;
+use std::{
+    fmt::{self, Display, Write},
+    ops::{Add, AddAssign},
+    time::Duration,
+};
+
+/// the average of the added values, unlike the averages of giputils its value is
+/// readable for the exported statistics
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Average {
+    sum: f64,
+    num: usize,
+}
+
+impl Average {
+    /// the average, zero if no value is added
+    pub fn value(&self) -> f64 {
+        if self.num == 0 {
+            0.0
+        } else {
+            self.sum / self.num as f64
+        }
+    }
+}
+
+impl AddAssign<f64> for Average {
+    fn add_assign(&mut self, rhs: f64) {
+        self.sum += rhs;
+        self.num += 1;
+    }
+}
+
+impl Add for Average {
+    type Output = Average;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            sum: self.sum + rhs.sum,
+            num: self.num + rhs.num,
+        }
+    }
+}
+
+/// the average of the added durations
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AverageDuration {
+    sum: Duration,
+    num: u32,
+}
+
+impl AverageDuration {
+    /// the average, zero if no duration is added
+    pub fn value(&self) -> Duration {
+        if self.num == 0 {
+            Duration::ZERO
+        } else {
+            self.sum / self.num
+        }
+    }
+}
+
+impl AddAssign<Duration> for AverageDuration {
+    fn add_assign(&mut self, rhs: Duration) {
+        self.sum += rhs;
+        self.num += 1;
+    }
+}
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct SolverStatistic {
     pub num_solve: usize,
     pub avg_decide_var: Average,
+    pub cone_precompute_time: Duration,
+    pub cone_cache_size: usize,
+    pub domain_time: Duration,
+    pub num_conflict: usize,
+    pub num_decision: usize,
+    pub num_propagation: usize,
+    pub num_restart: usize,
+    pub num_learnt: usize,
+    pub num_deleted: usize,
+    pub num_gc: usize,
+    pub num_recycle: usize,
+    pub num_import: usize,
 }
 
 impl Add for SolverStatistic {
@@ -14,6 +91,18 @@ impl Add for SolverStatistic {
         Self {
             num_solve: self.num_solve + rhs.num_solve,
             avg_decide_var: self.avg_decide_var + rhs.avg_decide_var,
+            cone_precompute_time: self.cone_precompute_time + rhs.cone_precompute_time,
+            cone_cache_size: self.cone_cache_size + rhs.cone_cache_size,
+            domain_time: self.domain_time + rhs.domain_time,
+            num_conflict: self.num_conflict + rhs.num_conflict,
+            num_decision: self.num_decision + rhs.num_decision,
+            num_propagation: self.num_propagation + rhs.num_propagation,
+            num_restart: self.num_restart + rhs.num_restart,
+            num_learnt: self.num_learnt + rhs.num_learnt,
+            num_deleted: self.num_deleted + rhs.num_deleted,
+            num_gc: self.num_gc + rhs.num_gc,
+            num_recycle: self.num_recycle + rhs.num_recycle,
+            num_import: self.num_import + rhs.num_import,
         }
     }
 }
@@ -22,4 +111,151 @@ impl Add for SolverStatistic {
 pub struct GipSATStatistic {
     pub num_sat: usize,
     pub avg_sat_time: AverageDuration,
+    pub avg_sat_lift_time: AverageDuration,
+    pub avg_sat_lift_len: Average,
+    pub avg_ternary_lift_time: AverageDuration,
+    pub avg_ternary_lift_len: Average,
+    pub num_ternary_lift_fail: usize,
+    pub inductive_time: Duration,
+    pub bad_time: Duration,
+    pub lift_time: Duration,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FrameStatistic {
+    pub num_lemma: usize,
+    /// solves of the solver of the frame, zero in single solver mode
+    pub num_solve: usize,
+}
+
+/// statistics of GipSAT, the solver counters are summed over the frame solvers
+/// and the lift solver
+#[derive(Debug, Default, Clone)]
+pub struct Statistics {
+    pub frames: Vec<FrameStatistic>,
+    /// number of solvers including the lift solver
+    pub num_solver: usize,
+    /// bytes allocated for clauses by all solvers
+    pub clause_memory: usize,
+    pub num_sat: usize,
+    pub num_solve: usize,
+    pub num_conflict: usize,
+    pub num_decision: usize,
+    pub num_propagation: usize,
+    pub num_restart: usize,
+    pub num_learnt: usize,
+    pub num_deleted: usize,
+    pub num_gc: usize,
+    pub num_recycle: usize,
+    pub num_import: usize,
+    pub num_ternary_lift_fail: usize,
+    /// size of the cached cones of the solvers
+    pub cone_cache_size: usize,
+    /// decision variables per solve
+    pub avg_decide_var: f64,
+    /// latchs of predecessors lifted by the lift solver
+    pub avg_sat_lift_len: f64,
+    /// latchs of predecessors lifted by ternary simulation
+    pub avg_ternary_lift_len: f64,
+    pub inductive_time: Duration,
+    pub bad_time: Duration,
+    /// time of getting predecessors, including failed ternary lifting
+    pub lift_time: Duration,
+    pub cone_precompute_time: Duration,
+    pub domain_time: Duration,
+    pub avg_sat_time: Duration,
+    pub avg_sat_lift_time: Duration,
+    pub avg_ternary_lift_time: Duration,
+}
+
+impl Statistics {
+    fn counters(&self) -> [(&'static str, usize); 15] {
+        [
+            ("num_solver", self.num_solver),
+            ("clause_memory", self.clause_memory),
+            ("num_sat", self.num_sat),
+            ("num_solve", self.num_solve),
+            ("num_conflict", self.num_conflict),
+            ("num_decision", self.num_decision),
+            ("num_propagation", self.num_propagation),
+            ("num_restart", self.num_restart),
+            ("num_learnt", self.num_learnt),
+            ("num_deleted", self.num_deleted),
+            ("num_gc", self.num_gc),
+            ("num_recycle", self.num_recycle),
+            ("num_import", self.num_import),
+            ("num_ternary_lift_fail", self.num_ternary_lift_fail),
+            ("cone_cache_size", self.cone_cache_size),
+        ]
+    }
+
+    fn averages(&self) -> [(&'static str, f64); 3] {
+        [
+            ("avg_decide_var", self.avg_decide_var),
+            ("avg_sat_lift_len", self.avg_sat_lift_len),
+            ("avg_ternary_lift_len", self.avg_ternary_lift_len),
+        ]
+    }
+
+    fn times(&self) -> [(&'static str, Duration); 8] {
+        [
+            ("inductive_time", self.inductive_time),
+            ("bad_time", self.bad_time),
+            ("lift_time", self.lift_time),
+            ("cone_precompute_time", self.cone_precompute_time),
+            ("domain_time", self.domain_time),
+            ("avg_sat_time", self.avg_sat_time),
+            ("avg_sat_lift_time", self.avg_sat_lift_time),
+            ("avg_ternary_lift_time", self.avg_ternary_lift_time),
+        ]
+    }
+
+    /// serialize as a json object, durations are given in seconds
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"frames\":[");
+        for (i, f) in self.frames.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"num_lemma\":{},\"num_solve\":{}}}",
+                f.num_lemma, f.num_solve
+            )
+            .unwrap();
+        }
+        json.push(']');
+        for (name, value) in self.counters() {
+            write!(json, ",\"{name}\":{value}").unwrap();
+        }
+        for (name, value) in self.averages() {
+            write!(json, ",\"{name}\":{value}").unwrap();
+        }
+        for (name, value) in self.times() {
+            write!(json, ",\"{name}\":{}", value.as_secs_f64()).unwrap();
+        }
+        json.push('}');
+        json
+    }
+}
+
+impl Display for Statistics {
+    /// the lemma numbers of the frames in a line followed by a line per counter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "frames:")?;
+        for frame in self.frames.iter() {
+            write!(f, " {}", frame.num_lemma)?;
+        }
+        writeln!(f)?;
+        for (name, value) in self.counters() {
+            writeln!(f, "{name}: {value}")?;
+        }
+        for (name, value) in self.averages() {
+            writeln!(f, "{name}: {value:.2}")?;
+        }
+        for (name, value) in self.times() {
+            writeln!(f, "{name}: {:.3}s", value.as_secs_f64())?;
+        }
+        Ok(())
+    }
 }
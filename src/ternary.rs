@@ -0,0 +1,223 @@
+use crate::utils::Lbool;
+use logic_form::{Cube, Lit, Var, VarMap};
+use std::{mem::take, rc::Rc};
+use transys::Transys;
+
+pub struct TernarySim {
+    ts: Rc<Transys>,
+    value: VarMap<Lbool>,
+    define: VarMap<Vec<usize>>,
+    order: Vec<Var>,
+    mark: VarMap<bool>,
+}
+
+impl TernarySim {
+    pub fn new(ts: &Rc<Transys>) -> Self {
+        let max_var = Var::new(ts.num_var);
+        let mut value = VarMap::new();
+        value.reserve(max_var);
+        let mut mark = VarMap::new();
+        mark.reserve(max_var);
+        let mut define: VarMap<Vec<usize>> = VarMap::new();
+        define.reserve(max_var);
+        for v in ts.inputs.iter().chain(ts.latchs.iter()) {
+            mark[*v] = true;
+        }
+        for (i, cls) in ts.trans.iter().enumerate() {
+            for l in cls.iter() {
+                let v = l.var();
+                if mark[v] {
+                    continue;
+                }
+                let dep = &ts.dependence[v];
+                if cls.iter().all(|o| o.var() == v || dep.contains(&o.var())) {
+                    define[v].push(i);
+                    break;
+                }
+            }
+        }
+        let mut order = Vec::new();
+        for v in 0..ts.num_var {
+            let v = Var::new(v);
+            if mark[v] {
+                continue;
+            }
+            let mut stack = vec![(v, 0)];
+            mark[v] = true;
+            while let Some((v, i)) = stack.pop() {
+                if i < ts.dependence[v].len() {
+                    stack.push((v, i + 1));
+                    let d = ts.dependence[v][i];
+                    if !mark[d] {
+                        mark[d] = true;
+                        stack.push((d, 0));
+                    }
+                } else if !define[v].is_empty() {
+                    order.push(v);
+                }
+            }
+        }
+        for m in mark.iter_mut() {
+            *m = false;
+        }
+        Self {
+            ts: ts.clone(),
+            value,
+            define,
+            order,
+            mark,
+        }
+    }
+
+    #[inline]
+    fn v(&self, lit: Lit) -> Lbool {
+        Lbool(self.value[lit].0 ^ (!lit.polarity() as u8))
+    }
+
+    #[inline]
+    fn set(&mut self, lit: Lit) {
+        self.value[lit] = Lbool::from(lit.polarity())
+    }
+
+    fn eval(&self, var: Var) -> Lbool {
+        'cls: for i in self.define[var].iter() {
+            let mut pivot = None;
+            for l in self.ts.trans[*i].iter() {
+                if l.var() == var {
+                    pivot = Some(*l);
+                } else if !self.v(*l).is_false() {
+                    continue 'cls;
+                }
+            }
+            return Lbool::from(pivot.unwrap().polarity());
+        }
+        Lbool::NONE
+    }
+
+    fn simulate(&mut self, order: &[Var]) {
+        for v in order.iter() {
+            self.value[*v] = self.eval(*v);
+        }
+    }
+
+    #[inline]
+    fn satisfied(&self, target: &[Lit]) -> bool {
+        target.iter().all(|l| self.v(*l).is_true())
+    }
+
+    /// recompute the variables defined by the transition relation in the model from
+    /// the values of its inputs and latchs, the model holds a literal per variable
+    pub fn simulate_model(&mut self, model: &mut Cube) {
+        let ts = self.ts.clone();
+        for v in ts.inputs.iter().chain(ts.latchs.iter()) {
+            self.set(model[Into::<usize>::into(*v)]);
+        }
+        let order = take(&mut self.order);
+        self.simulate(&order);
+        for v in order.iter() {
+            let value = self.value[*v];
+            if !value.is_none() {
+                model[Into::<usize>::into(*v)] = Lit::new(*v, value.is_true());
+            }
+            self.value[*v] = Lbool::NONE;
+        }
+        self.order = order;
+        for v in ts.inputs.iter().chain(ts.latchs.iter()) {
+            self.value[*v] = Lbool::NONE;
+        }
+    }
+
+    /// lift the latches with ternary simulation, keeps the latches whose X value
+    /// makes some target literal unknown, returns None if the assignment does not
+    /// justify the target
+    pub fn lift(&mut self, inputs: &[Lit], latchs: &[Lit], target: &[Lit]) -> Option<Cube> {
+        let mut coi = Vec::new();
+        for t in target.iter() {
+            if !self.mark[*t] {
+                self.mark[*t] = true;
+                coi.push(t.var());
+            }
+        }
+        let mut now = 0;
+        while now < coi.len() {
+            let v = coi[now];
+            now += 1;
+            for d in self.ts.dependence[v].iter() {
+                if !self.mark[*d] {
+                    self.mark[*d] = true;
+                    coi.push(*d);
+                }
+            }
+        }
+        for v in coi.iter() {
+            self.value[*v] = Lbool::NONE;
+        }
+        for l in inputs.iter().chain(latchs.iter()) {
+            self.set(*l);
+        }
+        let order: Vec<Var> = self
+            .order
+            .iter()
+            .filter(|v| self.mark[**v])
+            .copied()
+            .collect();
+        self.simulate(&order);
+        let res = if self.satisfied(target) {
+            let mut keep = vec![true; latchs.len()];
+            for i in (0..latchs.len()).rev() {
+                let l = latchs[i];
+                if !self.mark[l] {
+                    keep[i] = false;
+                    continue;
+                }
+                self.value[l] = Lbool::NONE;
+                self.simulate(&order);
+                if self.satisfied(target) {
+                    keep[i] = false;
+                } else {
+                    self.set(l);
+                    self.simulate(&order);
+                }
+            }
+            Some(
+                latchs
+                    .iter()
+                    .zip(keep)
+                    .filter_map(|(l, k)| k.then_some(*l))
+                    .collect(),
+            )
+        } else {
+            None
+        };
+        for v in coi.iter() {
+            self.mark[*v] = false;
+        }
+        for l in inputs.iter().chain(latchs.iter()) {
+            self.value[*l] = Lbool::NONE;
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::load, GipSAT, LiftMode};
+    use logic_form::Cube;
+
+    /// two latchs initialized to zero shifting an input, both latchs are bad
+    const SHIFT: &str = "aag 4 1 2 1 1\n2\n4 2\n6 4\n8\n8 4 6\n";
+
+    #[test]
+    fn ternary_lift_agrees_with_sat_lift() {
+        let ts = load("ternary_lift", SHIFT);
+        let (a, b) = (ts.latchs[0].lit(), ts.latchs[1].lit());
+        let mut gipsat = GipSAT::new(ts);
+        for _ in 0..3 {
+            gipsat.extend();
+        }
+        for mode in [LiftMode::Sat, LiftMode::Ternary] {
+            assert!(!gipsat.inductive(2, &[b], true));
+            assert_eq!(gipsat.get_predecessor_with(mode), Cube::from([a]));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{ic3::Step, ternary::TernarySim};
+use aig::Aig;
+use logic_form::{Cube, Lit, Var};
+use std::{env, fs, process, rc::Rc};
+use transys::Transys;
+
+/// load the transition system of an ascii aiger model for a test, the file is
+/// unique to the test name and the process so that tests may run in parallel
+pub fn load(name: &str, aag: &str) -> Rc<Transys> {
+    let path = env::temp_dir().join(format!("gipsat_{}_{name}.aag", process::id()));
+    fs::write(&path, aag).unwrap();
+    let aig = Aig::from_file(path.to_str().unwrap());
+    fs::remove_file(&path).unwrap();
+    Rc::new(Transys::from_aig(&aig, true))
+}
+
+/// simulate the step on the transition system, the inputs and latchs left out are
+/// false, returns a literal per variable
+pub fn simulate(ts: &Rc<Transys>, step: &Step) -> Cube {
+    let mut model: Cube = (0..ts.num_var)
+        .map(|v| Lit::new(Var::new(v), false))
+        .collect();
+    for l in step.latchs.iter().chain(step.inputs.iter()) {
+        model[Into::<usize>::into(l.var())] = *l;
+    }
+    TernarySim::new(ts).simulate_model(&mut model);
+    model
+}
+
+/// check that the trace is a path of the transition system from an initial state
+pub fn assert_path(ts: &Rc<Transys>, trace: &[Step]) {
+    for l in trace[0].latchs.iter() {
+        assert_ne!(ts.init_map.get(&l.var()), Some(&!l.polarity()));
+    }
+    for k in 1..trace.len() {
+        let model = simulate(ts, &trace[k - 1]);
+        for l in trace[k].latchs.iter() {
+            let next = ts.lit_next(l.var().lit());
+            let value = model[Into::<usize>::into(next.var())];
+            assert_eq!(value.polarity() == next.polarity(), l.polarity());
+        }
+    }
+}
+
+/// check that the trace is a path of the transition system from an initial state
+/// ending in a bad state
+pub fn assert_counterexample(ts: &Rc<Transys>, trace: &[Step]) {
+    assert_path(ts, trace);
+    let model = simulate(ts, &trace[trace.len() - 1]);
+    for b in ts.bad.iter() {
+        assert_eq!(model[Into::<usize>::into(b.var())], *b);
+    }
+}
@@ -578,6 +578,7 @@ impl Solver {
                 };
                 self.pos_in_trail.push(self.trail.len());
                 self.assign(decide, CREF_NONE);
+                self.statistic.num_decision += 1;
                 return true;
             }
         }
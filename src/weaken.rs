@@ -0,0 +1,158 @@
+use crate::{Call, GipSAT, CREF_NONE};
+use logic_form::Cube;
+
+impl GipSAT {
+    /// remove the lemma from the frame, its clause and the learnt clauses which may
+    /// be derived from it are removed from the solvers holding it, a solver is only
+    /// rebuilt if an assignment of level zero may depend on them. returns whether
+    /// the lemma was found, the lemmas of the initial frame are never removed
+    pub fn remove_lemma(&mut self, frame: usize, lemma: &Cube) -> bool {
+        self.recorded(
+            |gipsat| gipsat.remove_lemma_inner(frame, lemma),
+            |res| Call::RemoveLemma(frame, lemma.clone(), *res),
+        )
+    }
+
+    fn remove_lemma_inner(&mut self, frame: usize, lemma: &Cube) -> bool {
+        if frame == 0 || frame >= self.frame.len() {
+            return false;
+        }
+        let lemma = logic_form::Lemma::new(lemma.clone());
+        let Some((id, _)) = self
+            .index
+            .subsumed(&lemma, |f| f == frame)
+            .into_iter()
+            .find(|(id, _)| *self.index.lemma(*id) == lemma)
+        else {
+            return false;
+        };
+        let j = self.frame[frame].iter().position(|l| l.id == id).unwrap();
+        let l = self.frame[frame].swap_remove(j);
+        self.index.remove(id);
+        self.last_ind = None;
+        for (k, cref) in (l.begin..).zip(l.cref.iter()) {
+            if !self.solvers[k].retract_lemma(*cref) {
+                self.rebuild_solver(k);
+            }
+        }
+        true
+    }
+
+    /// drop the frames above the level, the lemmas of the dropped frames are kept
+    /// in the frame of the level as they hold there too, except for the initial
+    /// frame which only keeps its own lemmas
+    pub fn reset_to_level(&mut self, level: usize) {
+        self.recorded(
+            |gipsat| gipsat.reset_to_level_inner(level),
+            |_| Call::ResetToLevel(level),
+        )
+    }
+
+    fn reset_to_level_inner(&mut self, level: usize) {
+        assert!(level <= self.level());
+        self.last_ind = None;
+        let dropped: Vec<_> = self.frame.drain(level + 1..).flatten().collect();
+        if self.shared {
+            for l in dropped.iter() {
+                if l.cref[0] != CREF_NONE {
+                    self.solvers[0].remove_lemma(l.cref[0]);
+                }
+                self.index.remove(l.id);
+            }
+            self.drop_act(level);
+            if level > 0 {
+                for l in dropped {
+                    self.add_lemma_inner(level, l.lemma.cube().clone());
+                }
+            }
+            self.early = self.early.min(level).max(1);
+            return;
+        }
+        self.solvers.truncate(level + 1);
+        for mut l in dropped {
+            if level == 0 || l.begin > level {
+                // the solvers holding its clause are dropped
+                self.index.remove(l.id);
+                if level > 0 {
+                    self.add_lemma_inner(level, l.lemma.cube().clone());
+                }
+                continue;
+            }
+            l.cref.truncate(level + 1 - l.begin);
+            self.index.set_frame(l.id, level);
+            self.frame[level].push(l);
+        }
+        self.early = self.early.min(level).max(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::load, GipSAT};
+    use logic_form::Cube;
+
+    /// a latch initialized to zero taking the value of an input, the latch is bad
+    const LATCH: &str = "aag 2 1 1 1 0\n2\n4 2\n4\n";
+
+    /// two latchs initialized to zero shifting an input, both latchs are bad
+    const SHIFT: &str = "aag 4 1 2 1 1\n2\n4 2\n6 4\n8\n8 4 6\n";
+
+    fn gipsat(name: &str, aag: &str, shared: bool) -> GipSAT {
+        let ts = load(name, aag);
+        let mut gipsat = if shared {
+            GipSAT::new_shared(ts)
+        } else {
+            GipSAT::new(ts)
+        };
+        gipsat.extend();
+        gipsat
+    }
+
+    #[test]
+    fn removed_lemma_no_longer_blocks() {
+        for (aag, level) in [(LATCH, 1), (SHIFT, 2)] {
+            for shared in [false, true] {
+                let mut gipsat = gipsat(&format!("removed_{level}_{shared}"), aag, shared);
+                let lemma: Cube = gipsat.ts.latchs.iter().map(|l| l.lit()).collect();
+                for _ in 0..level {
+                    gipsat.extend();
+                }
+                assert!(gipsat.has_bad());
+                gipsat.add_lemma(level, lemma.clone());
+                assert!(!gipsat.has_bad());
+                assert!(gipsat.remove_lemma(level, &lemma));
+                assert!(gipsat.has_bad());
+                assert!(!gipsat.remove_lemma(level, &lemma));
+                assert!(!gipsat.remove_lemma(0, &lemma));
+                assert!(!gipsat.remove_lemma(level + 1, &lemma));
+            }
+        }
+    }
+
+    #[test]
+    fn reset_keeps_lemmas_of_dropped_frames() {
+        for shared in [false, true] {
+            let mut gipsat = gipsat(&format!("reset_{shared}"), SHIFT, shared);
+            let latchs: Cube = gipsat.ts.latchs.iter().map(|l| l.lit()).collect();
+            for _ in 0..3 {
+                gipsat.extend();
+            }
+            gipsat.add_lemma(2, Cube::from([latchs[0]]));
+            gipsat.add_lemma(3, latchs.clone());
+            gipsat.early = 3;
+            gipsat.reset_to_level(1);
+            assert_eq!(gipsat.level(), 1);
+            assert_eq!(gipsat.early, 1);
+            assert!(gipsat
+                .frame_lemmas(1)
+                .any(|l| *l == Cube::from([latchs[0]])));
+            assert!(!gipsat.has_bad());
+            gipsat.reset_to_level(0);
+            assert_eq!(gipsat.level(), 0);
+            assert_eq!(gipsat.early, 1);
+            gipsat.extend();
+            gipsat.extend();
+            assert!(gipsat.has_bad());
+        }
+    }
+}